@@ -4,7 +4,7 @@ use bevy::{
     dev_tools::states::log_transitions, input::common_conditions::input_just_pressed, prelude::*,
 };
 
-use crate::screens::Screen;
+use crate::{game::rng::GameRng, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
     // Log `Screen` state transitions.
@@ -15,6 +15,9 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
     );
+
+    // Show the seed so a run can be reported and replayed with `--seed`.
+    app.add_systems(Startup, spawn_seed_label);
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
@@ -22,3 +25,19 @@ const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+fn spawn_seed_label(mut commands: Commands, rng: Res<GameRng>) {
+    commands.spawn((
+        Name::new("Seed Label"),
+        Text::new(format!("seed {}", rng.seed())),
+        TextFont::from_font_size(12.0),
+        TextColor(Color::srgba(1.0, 1.0, 1.0, 0.5)),
+        Node {
+            position_type: PositionType::Absolute,
+            right: px(4),
+            bottom: px(2),
+            ..default()
+        },
+        GlobalZIndex(i32::MAX),
+    ));
+}
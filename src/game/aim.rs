@@ -0,0 +1,61 @@
+//! Twin-stick aiming. The right stick or a touch drag sets where the player
+//! throws and a reticle shows it. Moving the mouse or tapping hands the aim
+//! back to the pointer.
+
+use bevy::{input::mouse::AccumulatedMouseMotion, prelude::*};
+
+use crate::{
+    AppSystems, PausableSystems, game::player::Player, input::ActionInput, screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<StickAim>();
+    app.add_systems(
+        Update,
+        update_stick_aim
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(Update, draw_reticle.run_if(in_state(Screen::Gameplay)));
+    app.add_systems(OnExit(Screen::Gameplay), reset_stick_aim);
+}
+
+/// Distance of the reticle from the player
+const RETICLE_DISTANCE: f32 = 64.0;
+const RETICLE_RADIUS: f32 = 6.0;
+
+/// The last direction of the right stick, `None` while the pointer aims
+#[derive(Resource, Debug, Default)]
+pub struct StickAim(pub Option<Dir2>);
+
+fn update_stick_aim(
+    input: ActionInput,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mut aim: ResMut<StickAim>,
+) {
+    if let Some(direction) = input.aim() {
+        aim.0 = Some(direction);
+    } else if mouse_motion.delta != Vec2::ZERO || input.touch().throw_just_pressed() {
+        aim.0 = None;
+    }
+}
+
+fn reset_stick_aim(mut aim: ResMut<StickAim>) {
+    aim.0 = None;
+}
+
+fn draw_reticle(
+    mut gizmos: Gizmos,
+    aim: Res<StickAim>,
+    player: Option<Single<&GlobalTransform, With<Player>>>,
+) {
+    let (Some(direction), Some(player)) = (aim.0, player) else {
+        return;
+    };
+    let center = player.translation().xy() + direction * RETICLE_DISTANCE;
+    let color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+    gizmos.circle_2d(center, RETICLE_RADIUS, color);
+    let tick = direction.perp() * RETICLE_RADIUS;
+    gizmos.line_2d(center - tick * 1.6, center - tick * 0.6, color);
+    gizmos.line_2d(center + tick * 0.6, center + tick * 1.6, color);
+}
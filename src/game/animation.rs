@@ -13,11 +13,11 @@ use crate::{
     audio::sound_effect,
     game::{
         level::{
-            bosses::{Phase1Assets, Phase2Assets, Phase3Assets},
             enemies::{EnemyAssets, EyeEnemyAssets},
             projectiles::ProjectileAssets,
         },
         player::{Player, PlayerAssets},
+        rng::GameRng,
     },
 };
 
@@ -33,15 +33,22 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-/// Update the sprite direction and animation state (idling/walking).
+/// Update the sprite direction and animation state (idling/walking/dashing).
 fn update_animation_state(mut anim_q: Query<(&mut AseAnimation, &PlayerAnimation), With<Player>>) {
     for (mut ase_sprite_animation, player) in anim_q.iter_mut() {
         match player.state {
             PlayerAnimationState::Idle => {
                 ase_sprite_animation.animation.play_loop("Idle");
+                ase_sprite_animation.animation.speed = PLAYER_ANIMATION_SPEED;
             }
             PlayerAnimationState::Walk => {
                 ase_sprite_animation.animation.play_loop("Walk");
+                ase_sprite_animation.animation.speed = PLAYER_ANIMATION_SPEED;
+            }
+            // The sprite has no dash tag, so it is a fast walk
+            PlayerAnimationState::Dash => {
+                ase_sprite_animation.animation.play_loop("Walk");
+                ase_sprite_animation.animation.speed = PLAYER_ANIMATION_SPEED * 3.0;
             }
         }
     }
@@ -52,6 +59,7 @@ fn update_animation_state(mut anim_q: Query<(&mut AseAnimation, &PlayerAnimation
 fn trigger_step_sound_effect(
     mut cmd: Commands,
     anim_assets: If<Res<AnimationAssets>>,
+    mut rng: ResMut<GameRng>,
     mut anim_q: Query<&PlayerAnimation>,
     mut anim_msg: MessageReader<AnimationEvents>,
 ) {
@@ -60,8 +68,12 @@ fn trigger_step_sound_effect(
             if animation.state == PlayerAnimationState::Walk {
                 match msg {
                     AnimationEvents::LoopCycleFinished(_entity) => {
-                        let rng = &mut rand::rng();
-                        let random_step = anim_assets.player.steps.choose(rng).unwrap().clone();
+                        let random_step = anim_assets
+                            .player
+                            .steps
+                            .choose(rng.cosmetic())
+                            .unwrap()
+                            .clone();
                         cmd.spawn(sound_effect(random_step));
                     }
                     AnimationEvents::Finished(_entity) => (),
@@ -71,6 +83,9 @@ fn trigger_step_sound_effect(
     }
 }
 
+/// Playback speed of the player's aseprite animations
+pub const PLAYER_ANIMATION_SPEED: f32 = 2.0;
+
 /// Component that tracks player's animation state.
 /// It is tightly bound to aseprite animation we use.
 #[derive(Component, Reflect)]
@@ -84,6 +99,7 @@ pub enum PlayerAnimationState {
     Walk,
     #[default]
     Idle,
+    Dash,
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -112,6 +128,8 @@ impl FromWorld for AnimationAssets {
                     assets.load("audio/sound_effects/player/att3.ogg"),
                 ],
                 pickup: assets.load("audio/sound_effects/player/pickup.ogg"),
+                charge: assets.load("audio/sound_effects/ui/start-short.ogg"),
+                shielded: assets.load("audio/sound_effects/projectile/clink1.ogg"),
                 damages: vec![
                     assets.load("audio/sound_effects/player/dmg1.ogg"),
                     assets.load("audio/sound_effects/player/dmg2.ogg"),
@@ -122,6 +140,7 @@ impl FromWorld for AnimationAssets {
             },
             enemies: EnemyAssets {
                 throw: assets.load("audio/sound_effects/enemies/throw.ogg"),
+                charge: assets.load("audio/sound_effects/ui/start-long.ogg"),
                 eye_enemy: EyeEnemyAssets {
                     aseprite: assets.load("textures/chars/eye-enemy.aseprite"),
                     damages: vec![
@@ -131,52 +150,6 @@ impl FromWorld for AnimationAssets {
                         assets.load("audio/sound_effects/enemies/eyes/dmg4.ogg"),
                     ],
                 },
-                phase1: Phase1Assets {
-                    aseprite: assets.load("textures/chars/boss1.aseprite"),
-                    attacks: vec![
-                        assets.load("audio/sound_effects/maya/att1.ogg"),
-                        assets.load("audio/sound_effects/maya/att2.ogg"),
-                        assets.load("audio/sound_effects/maya/att3.ogg"),
-                        assets.load("audio/sound_effects/maya/att4.ogg"),
-                    ],
-                    damages: vec![
-                        assets.load("audio/sound_effects/maya/dmg1.ogg"),
-                        assets.load("audio/sound_effects/maya/dmg2.ogg"),
-                        assets.load("audio/sound_effects/maya/dmg3.ogg"),
-                        assets.load("audio/sound_effects/maya/dmg4.ogg"),
-                    ],
-                    idle: assets.load("audio/sound_effects/maya/idle.ogg"),
-                },
-                phase2: Phase2Assets {
-                    attacks: vec![
-                        assets.load("audio/sound_effects/mura/att1.ogg"),
-                        assets.load("audio/sound_effects/mura/att2.ogg"),
-                    ],
-                    grunts: vec![
-                        assets.load("audio/sound_effects/mura/grunt1.ogg"),
-                        assets.load("audio/sound_effects/mura/grunt2.ogg"),
-                        assets.load("audio/sound_effects/mura/grunt3.ogg"),
-                    ],
-                    intro: assets.load("audio/sound_effects/mura/intro.ogg"),
-                    damages: vec![
-                        assets.load("audio/sound_effects/mura/dmg1.ogg"),
-                        assets.load("audio/sound_effects/mura/dmg2.ogg"),
-                        assets.load("audio/sound_effects/mura/dmg3.ogg"),
-                    ],
-                },
-                phase3: Phase3Assets {
-                    aseprite: assets.load("textures/chars/boss3.aseprite"),
-                    attacks: vec![
-                        assets.load("audio/sound_effects/narak/att1.ogg"),
-                        assets.load("audio/sound_effects/narak/att2.ogg"),
-                    ],
-                    damages: vec![
-                        assets.load("audio/sound_effects/narak/dmg1.ogg"),
-                        assets.load("audio/sound_effects/narak/dmg2.ogg"),
-                        assets.load("audio/sound_effects/narak/dmg3.ogg"),
-                    ],
-                    death: assets.load("audio/sound_effects/narak/death.ogg"),
-                },
                 bullet: assets.load("textures/props/bullet.png"),
             },
             projectiles: ProjectileAssets {
@@ -185,7 +158,7 @@ impl FromWorld for AnimationAssets {
                     assets.load("audio/sound_effects/projectile/clink2.ogg"),
                     assets.load("audio/sound_effects/projectile/clink3.ogg"),
                 ],
-                bullet: assets.load("textures/props/bullet.png"),
+                fx: assets.load("textures/props/projectile-fx.aseprite"),
             },
         }
     }
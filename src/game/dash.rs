@@ -0,0 +1,136 @@
+//! The player's dash: a short burst in the walking direction with a window of
+//! invincibility frames at its start, for slipping through rings and sweeps.
+//! The [`Dash`] component holds the settings and the cooldown.
+
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        level::bosses::BossIntroPlaying,
+        movement::MovementController,
+        player::{Invulnerable, Player},
+    },
+    input::{Action, action_just_pressed},
+    screens::Screen,
+    ui::dialogue::DialogueQueue,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_dash,
+            start_dash.run_if(
+                in_state(Screen::Gameplay)
+                    .and(action_just_pressed(Action::Dash))
+                    .and(not(resource_exists::<DialogueQueue>))
+                    .and(not(any_with_component::<BossIntroPlaying>)),
+            ),
+        )
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Dash {
+    /// World units covered by one dash
+    pub distance: f32,
+    /// Seconds the dash lasts
+    pub duration: f32,
+    /// Seconds from the start of one dash to the next
+    pub cooldown: f32,
+    /// Seconds of invincibility from the start of the dash, see
+    /// [`Invulnerable::dodge`]
+    pub iframes: f32,
+    /// Seconds until the next dash
+    pub cooldown_left: f32,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        Self {
+            distance: 80.0,
+            duration: 0.18,
+            cooldown: 0.8,
+            iframes: 0.25,
+            cooldown_left: 0.0,
+        }
+    }
+}
+
+impl Dash {
+    pub fn speed(&self) -> f32 {
+        self.distance / self.duration
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown_left <= 0.0
+    }
+
+    /// 0 right after a dash, 1 when the next one is ready
+    pub fn readiness(&self) -> f32 {
+        if self.cooldown <= 0.0 {
+            1.0
+        } else {
+            1.0 - (self.cooldown_left / self.cooldown).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// Inserted while dashing, overrides the [`MovementController`] intent
+#[derive(Component, Debug)]
+pub struct Dashing {
+    pub direction: Dir2,
+    pub secs_left: f32,
+}
+
+fn start_dash(
+    mut commands: Commands,
+    player: Single<
+        (
+            Entity,
+            &mut Dash,
+            &mut Invulnerable,
+            &MovementController,
+            &Transform,
+        ),
+        (With<Player>, Without<Dashing>),
+    >,
+) {
+    let (entity, mut dash, mut invulnerable, controller, transform) = player.into_inner();
+    if !dash.is_ready() {
+        return;
+    }
+    // Standing still dashes the way the player faces
+    let direction = Dir2::new(controller.intent).unwrap_or(if transform.scale.x < 0.0 {
+        Dir2::NEG_X
+    } else {
+        Dir2::X
+    });
+    dash.cooldown_left = dash.cooldown;
+    invulnerable.dodge(dash.iframes);
+    commands.entity(entity).insert(Dashing {
+        direction,
+        secs_left: dash.duration,
+    });
+}
+
+fn tick_dash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Dash, Option<&mut Dashing>)>,
+) {
+    let delta = time.delta();
+    for (entity, mut dash, dashing) in &mut query {
+        dash.cooldown_left = (dash.cooldown_left - delta.as_secs_f32()).max(0.0);
+        if let Some(mut dashing) = dashing {
+            dashing.secs_left -= delta.as_secs_f32();
+            if dashing.secs_left <= 0.0 {
+                commands.entity(entity).remove::<Dashing>();
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! How hard the game is. The balance numbers stay written for Normal and go
+//! through [`Difficulty`] where they are used: boss life (and every life
+//! threshold of a boss), enemy bullet speed, bullets per pattern and the
+//! player's hearts and ammo.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Difficulty>();
+}
+
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    Story,
+    #[default]
+    Normal,
+    Hard,
+    Custom(DifficultyScale),
+}
+
+/// Multipliers over the Normal numbers
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct DifficultyScale {
+    pub boss_life: f32,
+    pub bullet_speed: f32,
+    /// Bullets in a [`ShootingPattern`](crate::game::level::enemy_behavior::ShootingPattern)
+    /// that fires more than one
+    pub bullet_density: f32,
+    /// Added to the hearts and ammo of [`Level::player_stats`](crate::game::level::Level::player_stats)
+    pub player_stats: i32,
+}
+
+impl DifficultyScale {
+    pub const STORY: Self = Self {
+        boss_life: 0.6,
+        bullet_speed: 0.75,
+        bullet_density: 0.6,
+        player_stats: 2,
+    };
+    pub const NORMAL: Self = Self {
+        boss_life: 1.0,
+        bullet_speed: 1.0,
+        bullet_density: 1.0,
+        player_stats: 0,
+    };
+    pub const HARD: Self = Self {
+        boss_life: 1.5,
+        bullet_speed: 1.25,
+        bullet_density: 1.5,
+        player_stats: -1,
+    };
+
+    // Bounds of the custom sliders
+    pub const MULTIPLIER_RANGE: (f32, f32) = (0.5, 2.0);
+    pub const PLAYER_STATS_RANGE: (i32, i32) = (-2, 3);
+}
+
+impl Difficulty {
+    pub fn scale(&self) -> DifficultyScale {
+        match self {
+            Difficulty::Story => DifficultyScale::STORY,
+            Difficulty::Normal => DifficultyScale::NORMAL,
+            Difficulty::Hard => DifficultyScale::HARD,
+            Difficulty::Custom(scale) => *scale,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Story => "Story",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Custom(_) => "Custom",
+        }
+    }
+
+    /// Cycles Story, Normal, Hard, Custom. Custom starts from Hard's numbers.
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Story => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Custom(DifficultyScale::HARD),
+            Difficulty::Custom(_) => Difficulty::Story,
+        }
+    }
+
+    /// Custom starts from Story's numbers going this way
+    pub fn previous(&self) -> Self {
+        match self {
+            Difficulty::Story => Difficulty::Custom(DifficultyScale::STORY),
+            Difficulty::Normal => Difficulty::Story,
+            Difficulty::Hard => Difficulty::Normal,
+            Difficulty::Custom(_) => Difficulty::Hard,
+        }
+    }
+
+    /// Boss life, or a life threshold of a boss
+    pub fn boss_life(&self, life: usize) -> usize {
+        scale_life(life, self.scale().boss_life)
+    }
+
+    pub fn bullet_speed(&self, speed: f32) -> f32 {
+        speed * self.scale().bullet_speed
+    }
+
+    /// Single shots stay single, and no pattern goes below one bullet
+    pub fn bullet_count(&self, count: usize) -> usize {
+        if count <= 1 {
+            return count;
+        }
+        ((count as f32 * self.scale().bullet_density).round() as usize).max(1)
+    }
+
+    pub fn player_stats(&self, stats: usize) -> usize {
+        (stats as i32 + self.scale().player_stats).max(1) as usize
+    }
+}
+
+/// Rounded, and anything alive stays alive
+pub fn scale_life(life: usize, scale: f32) -> usize {
+    ((life as f32 * scale).round() as usize).max(life.min(1))
+}
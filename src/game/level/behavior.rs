@@ -0,0 +1,202 @@
+//! Enemy behavior written as data: a [`Behavior`] is a list of named states,
+//! each with its moves, its attacks and the transitions out of it.
+//! Entering a state hands its moves and attacks to the [`Enemy`]; the usual
+//! move and shooting systems take it from there.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    PausableSystems,
+    game::{
+        level::{
+            boss_phases::PhaseTransition,
+            bosses::BossIntroPlaying,
+            enemies::Enemy,
+            enemy_behavior::{EnemyAttack, Move},
+        },
+        player::Player,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(enter_first_state);
+    app.add_systems(Update, update_behaviors.in_set(PausableSystems));
+}
+
+#[derive(Component, Clone, Default)]
+pub struct Behavior {
+    pub states: Vec<BehaviorState>,
+    pub current: usize,
+    /// Seconds spent in the current state
+    pub elapsed: f32,
+    /// [`Enemy::attacks_finished`] when the current state was entered
+    pub attacks_at_enter: usize,
+}
+
+impl Behavior {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The first state added is where it starts
+    pub fn with_state(mut self, state: BehaviorState) -> Self {
+        self.states.push(state);
+        self
+    }
+
+    pub fn current_state(&self) -> Option<&BehaviorState> {
+        self.states.get(self.current)
+    }
+
+    /// Switch to the state at `index` and hand its moves and attacks to the enemy
+    pub fn enter(&mut self, index: usize, enemy: &mut Enemy) {
+        let Some(state) = self.states.get(index) else {
+            return;
+        };
+        enemy.moves.clear();
+        enemy.move_pattern = state.moves.clone();
+        enemy.attacks = state.attacks.clone();
+        enemy.attack_idx = 0;
+        self.current = index;
+        self.elapsed = 0.0;
+        self.attacks_at_enter = enemy.attacks_finished;
+    }
+}
+
+#[derive(Clone)]
+pub struct BehaviorState {
+    pub name: String,
+    pub moves: Vec<Move>,
+    pub attacks: Vec<EnemyAttack>,
+    /// Checked in order, the first one that holds wins
+    pub transitions: Vec<Transition>,
+}
+
+impl BehaviorState {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            moves: Vec::new(),
+            attacks: Vec::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    pub fn with_moves(mut self, moves: Vec<Move>) -> Self {
+        self.moves = moves;
+        self
+    }
+
+    pub fn with_attack(mut self, attack: EnemyAttack) -> Self {
+        self.attacks.push(attack);
+        self
+    }
+
+    pub fn with_transition(mut self, to: impl Into<String>, when: Condition) -> Self {
+        self.transitions.push(Transition {
+            to: to.into(),
+            when,
+        });
+        self
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Transition {
+    /// Name of the state to go to
+    pub to: String,
+    pub when: Condition,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Condition {
+    LifeAtMost(usize),
+    PlayerWithin(f32),
+    PlayerBeyond(f32),
+    /// Seconds in the state
+    After(f32),
+    /// Attacks that ran to the end since entering the state
+    AttacksFinished(usize),
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+}
+
+/// What a [`Condition`] is checked against
+pub struct ConditionContext {
+    pub life: usize,
+    pub player_distance: Option<f32>,
+    pub elapsed: f32,
+    pub attacks_finished: usize,
+}
+
+impl Condition {
+    pub fn holds(&self, ctx: &ConditionContext) -> bool {
+        match self {
+            Condition::LifeAtMost(life) => ctx.life <= *life,
+            Condition::PlayerWithin(d) => ctx.player_distance.is_some_and(|pd| pd <= *d),
+            Condition::PlayerBeyond(d) => ctx.player_distance.is_some_and(|pd| pd > *d),
+            Condition::After(secs) => ctx.elapsed >= *secs,
+            Condition::AttacksFinished(n) => ctx.attacks_finished >= *n,
+            Condition::All(conditions) => conditions.iter().all(|c| c.holds(ctx)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.holds(ctx)),
+        }
+    }
+
+    /// The same condition with every life in it passed through `f`
+    pub fn map_life(&self, f: &impl Fn(usize) -> usize) -> Condition {
+        match self {
+            Condition::LifeAtMost(life) => Condition::LifeAtMost(f(*life)),
+            Condition::All(conditions) => {
+                Condition::All(conditions.iter().map(|c| c.map_life(f)).collect())
+            }
+            Condition::Any(conditions) => {
+                Condition::Any(conditions.iter().map(|c| c.map_life(f)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+fn enter_first_state(add: On<Add, Behavior>, mut query: Query<(&mut Behavior, &mut Enemy)>) {
+    if let Ok((mut behavior, mut enemy)) = query.get_mut(add.entity) {
+        behavior.enter(0, &mut enemy);
+    }
+}
+
+fn update_behaviors(
+    time: Res<Time>,
+    player: Option<Single<&Transform, With<Player>>>,
+    query: Query<
+        (&mut Behavior, &mut Enemy, &Transform, Option<&Name>),
+        (Without<BossIntroPlaying>, Without<PhaseTransition>),
+    >,
+) {
+    let player_pos = player.map(|t| t.translation.xy());
+    for (mut behavior, mut enemy, transform, name) in query {
+        behavior.elapsed += time.delta_secs();
+        let Some(state) = behavior.current_state() else {
+            continue;
+        };
+        let ctx = ConditionContext {
+            life: enemy.life,
+            player_distance: player_pos.map(|p| p.distance(transform.translation.xy())),
+            elapsed: behavior.elapsed,
+            attacks_finished: enemy.attacks_finished - behavior.attacks_at_enter,
+        };
+        // Targets are checked when the definition loads
+        let Some(index) = state
+            .transitions
+            .iter()
+            .find(|t| t.when.holds(&ctx))
+            .and_then(|t| behavior.states.iter().position(|s| s.name == t.to))
+        else {
+            continue;
+        };
+        debug!(
+            "{:?}: {} -> {}",
+            name, state.name, behavior.states[index].name
+        );
+        behavior.enter(index, &mut enemy);
+    }
+}
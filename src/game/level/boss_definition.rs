@@ -0,0 +1,744 @@
+//! Bosses described in `assets/bosses/*.boss.ron` instead of Rust bundles.
+//! A [`BossDefinition`] holds everything that makes a boss: life, body,
+//! sprite, sounds, intro, behavior states and teleports. A definition with
+//! `phases` is a single fight that changes stage at life thresholds.
+//! [`spawn_boss`] builds the entity from it.
+//!
+//! Editing a definition while the game runs (with `file_watcher`) retunes the
+//! bosses already on the field.
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::system::EntityCommands,
+    prelude::*,
+};
+use bevy_aseprite_ultra::prelude::*;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::LoadResource,
+    game::{
+        difficulty::Difficulty,
+        level::{
+            Level,
+            behavior::{Behavior, BehaviorState, Transition},
+            boss_phases::PhaseProgress,
+            bosses::{BOSS_Z_TRANSLATION, Boss, BossFlow, BossIntroPlaying, BossPhase},
+            enemies::Enemy,
+            enemy_behavior::{Aim, AimMode, EnemyAttack, Move, ShootingPattern, Telegraph},
+            teleport::{TeleportAbility, TeleportChoice},
+        },
+        movement::ScreenWrap,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<BossDefinition>()
+        .register_asset_loader(BossDefinitionLoader)
+        .load_resource::<BossDefinitions>();
+    app.add_systems(
+        Update,
+        apply_boss_definition_changes.run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The boss of each level
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct BossDefinitions {
+    #[dependency]
+    pub tutorial: Handle<BossDefinition>,
+    #[dependency]
+    pub phase1: Handle<BossDefinition>,
+    #[dependency]
+    pub phase2: Handle<BossDefinition>,
+    #[dependency]
+    pub phase3: Handle<BossDefinition>,
+    /// All three phases as one fight, for [`BossFlow::SingleFight`]
+    #[dependency]
+    pub single_fight: Handle<BossDefinition>,
+}
+
+impl FromWorld for BossDefinitions {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            tutorial: assets.load("bosses/tutorial.boss.ron"),
+            phase1: assets.load("bosses/phase1.boss.ron"),
+            phase2: assets.load("bosses/phase2.boss.ron"),
+            phase3: assets.load("bosses/phase3.boss.ron"),
+            single_fight: assets.load("bosses/narak.boss.ron"),
+        }
+    }
+}
+
+impl BossDefinitions {
+    pub fn for_level(&self, level: Level, flow: BossFlow) -> &Handle<BossDefinition> {
+        use Level::*;
+        match level {
+            Tutorial => &self.tutorial,
+            Phase1 if flow == BossFlow::SingleFight => &self.single_fight,
+            Phase1 => &self.phase1,
+            Phase2 => &self.phase2,
+            Phase3 => &self.phase3,
+        }
+    }
+}
+
+#[derive(Asset, TypePath, Debug)]
+pub struct BossDefinition {
+    pub name: String,
+    pub life: usize,
+    /// Part of the three-phase health bar if set
+    pub phase: Option<u8>,
+    pub intro: BossIntro,
+    /// The fight at full life, then one more for each threshold in `phases`
+    pub stages: Vec<BossStage>,
+    #[dependency]
+    pub attack_sounds: Vec<Handle<AudioSource>>,
+    #[dependency]
+    pub damage_sounds: Vec<Handle<AudioSource>>,
+    #[dependency]
+    pub intro_sound: Option<Handle<AudioSource>>,
+}
+
+/// How the boss looks, moves and attacks over a stretch of its life
+#[derive(Debug)]
+pub struct BossStage {
+    /// Starts once life drops to this. The first stage starts at full life.
+    pub at_life: usize,
+    pub collider_radius: f32,
+    pub body: BossBody,
+    pub shooting_range: f32,
+    pub bullet_colors: Vec<Color>,
+    /// The first one is where it starts
+    pub states: Vec<StateDef>,
+    pub teleport: Option<TeleportDef>,
+    /// Played when the stage starts. The boss can't be hurt for its `secs`.
+    pub transition: Option<BossIntro>,
+    /// Loaded with the definition
+    pub aseprite: Handle<Aseprite>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BossBody {
+    Static,
+    Dynamic,
+    /// Slides left and right only
+    DynamicLockY,
+}
+
+impl BossBody {
+    pub(super) fn rigid_body(&self) -> RigidBody {
+        match self {
+            BossBody::Static => RigidBody::Static,
+            BossBody::Dynamic | BossBody::DynamicLockY => RigidBody::Dynamic,
+        }
+    }
+
+    pub(super) fn locked_axes(&self) -> LockedAxes {
+        match self {
+            BossBody::DynamicLockY => LockedAxes::new().lock_rotation().lock_translation_y(),
+            _ => LockedAxes::new().lock_rotation(), // To be resolved with later kinematic solution
+        }
+    }
+}
+
+/// What happens when the level fades in
+#[derive(Deserialize, Clone, Debug)]
+pub struct BossIntro {
+    /// Aseprite tag to play before fighting. Without it, the boss waits for the dialogue.
+    pub animation: Option<String>,
+    #[serde(default = "BossIntro::default_repeats")]
+    pub repeats: u32,
+    #[serde(default)]
+    pub secs: f32,
+    /// Camera trauma
+    #[serde(default)]
+    pub shake: f32,
+}
+
+impl BossIntro {
+    fn default_repeats() -> u32 {
+        1
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct StateDef {
+    pub name: String,
+    #[serde(default)]
+    pub moves: Vec<MoveDef>,
+    #[serde(default)]
+    pub attacks: Vec<AttackDef>,
+    #[serde(default)]
+    pub transitions: Vec<Transition>,
+}
+
+/// [`Move`] as written in a definition. Positions are relative to the spawn point.
+#[derive(Deserialize, Clone, Debug)]
+pub enum MoveDef {
+    Velocity {
+        velocity: [f32; 2],
+        secs: f32,
+    },
+    Waypoints {
+        points: Vec<[f32; 2]>,
+        speed: f32,
+    },
+    Orbit {
+        radius: f32,
+        angular_speed: f32,
+        secs: f32,
+    },
+    ChasePlayer {
+        speed: f32,
+        secs: f32,
+    },
+    SineDrift {
+        velocity: [f32; 2],
+        amplitude: f32,
+        frequency: f32,
+        secs: f32,
+    },
+    Dash {
+        toward_player: bool,
+        speed: f32,
+        wind_up_secs: f32,
+        secs: f32,
+    },
+}
+
+impl MoveDef {
+    pub(super) fn to_move(&self, origin: Vec2) -> Move {
+        match self {
+            MoveDef::Velocity { velocity, secs } => Move::UnitVelocity(
+                LinearVelocity(Vec2::from(*velocity)),
+                Timer::from_seconds(*secs, TimerMode::Once),
+            ),
+            MoveDef::Waypoints { points, speed } => Move::waypoints(
+                points.iter().map(|p| origin + Vec2::from(*p)).collect(),
+                *speed,
+            ),
+            MoveDef::Orbit {
+                radius,
+                angular_speed,
+                secs,
+            } => Move::orbit(*radius, *angular_speed, *secs),
+            MoveDef::ChasePlayer { speed, secs } => Move::chase_player(*speed, *secs),
+            MoveDef::SineDrift {
+                velocity,
+                amplitude,
+                frequency,
+                secs,
+            } => Move::sine_drift(Vec2::from(*velocity), *amplitude, *frequency, *secs),
+            MoveDef::Dash {
+                toward_player,
+                speed,
+                wind_up_secs,
+                secs,
+            } => Move::dash(*toward_player, *speed, *wind_up_secs, *secs),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AttackDef {
+    /// Seconds between shots
+    pub cooldown: f32,
+    /// Seconds before moving on to the next attack
+    pub duration: f32,
+    pub patterns: Vec<PatternDef>,
+    #[serde(default)]
+    pub aim: AimMode,
+    /// Degrees either way
+    #[serde(default)]
+    pub aim_error: f32,
+    #[serde(default)]
+    pub telegraph: Option<TelegraphDef>,
+    /// Teleport before starting, if the boss can
+    #[serde(default)]
+    pub teleport: bool,
+}
+
+impl AttackDef {
+    pub(super) fn to_attack(&self) -> EnemyAttack {
+        EnemyAttack {
+            cooldown_timer: Timer::from_seconds(self.cooldown, TimerMode::Repeating),
+            duration: Timer::from_seconds(self.duration, TimerMode::Once),
+            shooting_pattern: self.patterns.iter().map(PatternDef::to_pattern).collect(),
+            aim: Aim {
+                mode: self.aim,
+                error: self.aim_error.to_radians(),
+            },
+            telegraph: self.telegraph.as_ref().map(TelegraphDef::to_telegraph),
+            teleport: self.teleport,
+        }
+    }
+}
+
+/// Warning shown before an attack starts firing
+#[derive(Deserialize, Clone, Debug)]
+pub struct TelegraphDef {
+    pub lead_secs: f32,
+    /// Aseprite tag to play as a wind-up
+    #[serde(default)]
+    pub animation: Option<String>,
+    /// Draw where the patterns will fire
+    #[serde(default = "TelegraphDef::default_true")]
+    pub lines: bool,
+    /// Play the charge-up sound
+    #[serde(default = "TelegraphDef::default_true")]
+    pub sound: bool,
+}
+
+impl TelegraphDef {
+    fn default_true() -> bool {
+        true
+    }
+
+    fn to_telegraph(&self) -> Telegraph {
+        Telegraph {
+            lead: Timer::from_seconds(self.lead_secs, TimerMode::Once),
+            animation: self.animation.clone(),
+            lines: self.lines,
+            sound: self.sound,
+        }
+    }
+}
+
+/// [`ShootingPattern`] with angles in degrees
+#[derive(Deserialize, Clone, Debug)]
+pub enum PatternDef {
+    Straight,
+    Spread { count: usize, arc: f32 },
+    Ring { count: usize },
+    Flank { angle: f32 },
+    Random { count: usize, arc: f32 },
+    Sweep { arc: f32, clockwise: bool },
+}
+
+impl PatternDef {
+    fn to_pattern(&self) -> ShootingPattern {
+        match *self {
+            PatternDef::Straight => ShootingPattern::Straight,
+            PatternDef::Spread { count, arc } => ShootingPattern::Spread {
+                count,
+                arc: arc.to_radians(),
+            },
+            PatternDef::Ring { count } => ShootingPattern::Ring { count },
+            PatternDef::Flank { angle } => ShootingPattern::Flank {
+                angle: angle.to_radians(),
+            },
+            PatternDef::Random { count, arc } => ShootingPattern::Random {
+                count,
+                arc: arc.to_radians(),
+            },
+            PatternDef::Sweep { arc, clockwise } => ShootingPattern::Sweep {
+                arc: arc.to_radians(),
+                clockwise,
+            },
+        }
+    }
+}
+
+/// Blinks to one of `positions` (map coordinates) every `every_secs`
+#[derive(Deserialize, Clone, Debug)]
+pub struct TeleportDef {
+    pub positions: Vec<[f32; 2]>,
+    pub every_secs: f32,
+    #[serde(default)]
+    pub choice: TeleportChoice,
+    /// Fractions of the full life that also make it teleport, highest first
+    #[serde(default)]
+    pub at_life_fractions: Vec<f32>,
+    #[serde(default = "TeleportDef::default_clearance")]
+    pub clearance: f32,
+    #[serde(default = "TeleportDef::default_min_player_distance")]
+    pub min_player_distance: f32,
+    #[serde(default)]
+    pub disappear: Option<String>,
+    #[serde(default)]
+    pub reappear: Option<String>,
+    #[serde(default = "TeleportDef::default_fade_secs")]
+    pub fade_secs: f32,
+}
+
+impl TeleportDef {
+    fn default_clearance() -> f32 {
+        32.0
+    }
+
+    fn default_min_player_distance() -> f32 {
+        96.0
+    }
+
+    fn default_fade_secs() -> f32 {
+        0.3
+    }
+
+    /// `life` is the full life the fractions are taken of
+    pub(super) fn to_ability(&self, life: usize) -> TeleportAbility {
+        TeleportAbility {
+            positions: self.positions.iter().map(|p| Vec2::from(*p)).collect(),
+            timer: Timer::from_seconds(self.every_secs, TimerMode::Repeating),
+            current_index: 0,
+            choice: self.choice,
+            at_life: self
+                .at_life_fractions
+                .iter()
+                .map(|f| (f * life as f32).round() as usize)
+                .collect(),
+            clearance: self.clearance,
+            min_player_distance: self.min_player_distance,
+            disappear: self.disappear.clone(),
+            reappear: self.reappear.clone(),
+            fade_secs: self.fade_secs,
+            attack_run: None,
+        }
+    }
+}
+
+impl BossDefinition {
+    /// The stage at `index`, or the last one past the end
+    pub fn stage(&self, index: usize) -> &BossStage {
+        &self.stages[index.min(self.stages.len() - 1)]
+    }
+
+    /// Life at which each stage after the first starts
+    pub fn thresholds(&self, difficulty: &Difficulty) -> Vec<usize> {
+        self.stages
+            .iter()
+            .skip(1)
+            .map(|stage| difficulty.boss_life(stage.at_life))
+            .collect()
+    }
+}
+
+impl BossStage {
+    /// The behavior states with moves placed around `origin`, and life
+    /// conditions scaled like the boss life
+    pub fn behavior(&self, origin: Vec2, difficulty: &Difficulty) -> Behavior {
+        let scale = |life| difficulty.boss_life(life);
+        self.states.iter().fold(Behavior::new(), |behavior, def| {
+            let mut state = BehaviorState::new(def.name.clone())
+                .with_moves(def.moves.iter().map(|m| m.to_move(origin)).collect());
+            for attack in &def.attacks {
+                state = state.with_attack(attack.to_attack());
+            }
+            for transition in &def.transitions {
+                state =
+                    state.with_transition(transition.to.clone(), transition.when.map_life(&scale));
+            }
+            behavior.with_state(state)
+        })
+    }
+}
+
+/// Where a boss came from, so it can follow changes to its definition
+#[derive(Component, Debug)]
+pub struct BossSource {
+    pub definition: Handle<BossDefinition>,
+    pub origin: Vec2,
+}
+
+/// Spawns the boss described by `handle` at `xy`
+pub fn spawn_boss<'a>(
+    commands: &'a mut Commands,
+    handle: &Handle<BossDefinition>,
+    definition: &BossDefinition,
+    xy: Vec2,
+    difficulty: &Difficulty,
+) -> EntityCommands<'a> {
+    let stage = definition.stage(0);
+    let life = difficulty.boss_life(definition.life);
+    let mut boss = commands.spawn((
+        Name::new(definition.name.clone()),
+        Boss,
+        BossSource {
+            definition: handle.clone(),
+            origin: xy,
+        },
+        BossIntroPlaying,
+        Enemy::new(life)
+            .with_shooting_range(stage.shooting_range)
+            .with_bullet_colors(stage.bullet_colors.clone()),
+        stage.behavior(xy, difficulty),
+        AseAnimation {
+            animation: Animation::tag("Idle")
+                .with_repeat(AnimationRepeat::Loop)
+                .with_direction(AnimationDirection::Forward)
+                .with_speed(1.0),
+            aseprite: stage.aseprite.clone(),
+        },
+        Sprite::default(),
+        ScreenWrap,
+        stage.body.locked_axes(),
+        Transform::from_xyz(xy.x, xy.y, BOSS_Z_TRANSLATION),
+        stage.body.rigid_body(),
+        GravityScale(0.0),
+        Dominance(5), // dominates all dynamic bodies with a dominance lower than `5`.
+        Collider::circle(stage.collider_radius),
+    ));
+    if let Some(phase) = definition.phase {
+        boss.insert(BossPhase::for_phase(phase, difficulty.scale().boss_life));
+    }
+    if definition.stages.len() > 1 {
+        boss.insert(PhaseProgress::new(definition, difficulty));
+    }
+    if let Some(teleport) = &stage.teleport {
+        boss.insert(teleport.to_ability(life));
+    }
+    boss
+}
+
+/// Retune the bosses on the field when their definition file changes
+fn apply_boss_definition_changes(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<BossDefinition>>,
+    definitions: Res<Assets<BossDefinition>>,
+    difficulty: Res<Difficulty>,
+    mut boss_query: Query<(
+        Entity,
+        &BossSource,
+        &mut Enemy,
+        &Behavior,
+        &mut AseAnimation,
+        Option<&mut PhaseProgress>,
+    )>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        let Some(definition) = definitions.get(*id) else {
+            continue;
+        };
+        for (entity, source, mut enemy, old_behavior, mut animation, progress) in &mut boss_query {
+            if source.definition.id() != *id {
+                continue;
+            }
+            info!("Reloaded boss definition {:?}", definition.name);
+            let life = difficulty.boss_life(definition.life);
+            let current = match progress {
+                Some(mut progress) => {
+                    progress.thresholds = definition.thresholds(&difficulty);
+                    progress.max_life = life;
+                    progress.current
+                }
+                None => 0,
+            };
+            let stage = definition.stage(current);
+            enemy.life = enemy.life.min(life);
+            enemy.shooting_range = stage.shooting_range;
+            enemy.bullet_colors = stage.bullet_colors.clone();
+            // Stay in the same state if it still exists
+            let mut behavior = stage.behavior(source.origin, &difficulty);
+            let index = old_behavior
+                .current_state()
+                .and_then(|old| behavior.states.iter().position(|s| s.name == old.name))
+                .unwrap_or(0);
+            behavior.enter(index, &mut enemy);
+            animation.aseprite = stage.aseprite.clone();
+
+            let mut boss = commands.entity(entity);
+            boss.insert((
+                Name::new(definition.name.clone()),
+                behavior,
+                stage.body.locked_axes(),
+                stage.body.rigid_body(),
+                Collider::circle(stage.collider_radius),
+            ));
+            match &stage.teleport {
+                Some(teleport) => boss.insert(teleport.to_ability(life)),
+                None => boss.remove::<TeleportAbility>(),
+            };
+        }
+    }
+}
+
+#[derive(TypePath)]
+pub struct BossDefinitionLoader;
+
+#[derive(Debug, Error)]
+pub enum BossDefinitionLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load boss definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse boss definition: {0}")]
+    Ron(#[from] ron::de::SpannedError),
+    #[error("{name}: phase {phase} is not between 1 and {}", BossPhase::LAST_PHASE)]
+    InvalidPhase { name: String, phase: u8 },
+    #[error("{name}: stage {stage} has no behavior states")]
+    NoStates { name: String, stage: usize },
+    #[error("{name}: state {from:?} goes to {to:?}, which is not in stage {stage}")]
+    UnknownState {
+        name: String,
+        stage: usize,
+        from: String,
+        to: String,
+    },
+}
+
+/// The file as written, with asset paths instead of handles
+#[derive(Deserialize)]
+struct BossDefinitionFile {
+    name: String,
+    life: usize,
+    #[serde(default)]
+    phase: Option<u8>,
+    collider_radius: f32,
+    body: BossBody,
+    shooting_range: f32,
+    /// sRGB
+    bullet_colors: Vec<[f32; 3]>,
+    intro: BossIntro,
+    states: Vec<StateDef>,
+    #[serde(default)]
+    teleport: Option<TeleportDef>,
+    aseprite: String,
+    attack_sounds: Vec<String>,
+    damage_sounds: Vec<String>,
+    #[serde(default)]
+    intro_sound: Option<String>,
+    /// Later stages of the same fight, in the order they happen
+    #[serde(default)]
+    phases: Vec<BossPhaseFile>,
+}
+
+#[derive(Deserialize)]
+struct BossPhaseFile {
+    at_life: usize,
+    transition: BossIntro,
+    collider_radius: f32,
+    body: BossBody,
+    shooting_range: f32,
+    /// sRGB
+    bullet_colors: Vec<[f32; 3]>,
+    states: Vec<StateDef>,
+    #[serde(default)]
+    teleport: Option<TeleportDef>,
+    aseprite: String,
+}
+
+/// Every stage needs a first state, and transitions only go to states of their own stage
+fn check_states(
+    name: &str,
+    stage: usize,
+    states: &[StateDef],
+) -> Result<(), BossDefinitionLoaderError> {
+    if states.is_empty() {
+        return Err(BossDefinitionLoaderError::NoStates {
+            name: name.to_string(),
+            stage,
+        });
+    }
+    for state in states {
+        for transition in &state.transitions {
+            if !states.iter().any(|s| s.name == transition.to) {
+                return Err(BossDefinitionLoaderError::UnknownState {
+                    name: name.to_string(),
+                    stage,
+                    from: state.name.clone(),
+                    to: transition.to.clone(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn srgb_colors(colors: &[[f32; 3]]) -> Vec<Color> {
+    colors
+        .iter()
+        .map(|[r, g, b]| Color::srgb(*r, *g, *b))
+        .collect()
+}
+
+impl AssetLoader for BossDefinitionLoader {
+    type Asset = BossDefinition;
+    type Settings = ();
+    type Error = BossDefinitionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: BossDefinitionFile = ron::de::from_bytes(&bytes)?;
+        if let Some(phase) = file.phase
+            && !(1..=BossPhase::LAST_PHASE).contains(&phase)
+        {
+            return Err(BossDefinitionLoaderError::InvalidPhase {
+                name: file.name,
+                phase,
+            });
+        }
+        // Stages are counted from 1, like phases
+        check_states(&file.name, 1, &file.states)?;
+        for (i, phase) in file.phases.iter().enumerate() {
+            check_states(&file.name, i + 2, &phase.states)?;
+        }
+
+        let first = BossStage {
+            at_life: file.life,
+            collider_radius: file.collider_radius,
+            body: file.body,
+            shooting_range: file.shooting_range,
+            bullet_colors: srgb_colors(&file.bullet_colors),
+            states: file.states,
+            teleport: file.teleport,
+            transition: None,
+            aseprite: load_context.load(file.aseprite),
+        };
+        let mut stages = vec![first];
+        for phase in file.phases {
+            if phase.at_life >= stages[stages.len() - 1].at_life {
+                warn!(
+                    "{}: phase at life {} comes after a lower threshold",
+                    file.name, phase.at_life
+                );
+            }
+            stages.push(BossStage {
+                at_life: phase.at_life,
+                collider_radius: phase.collider_radius,
+                body: phase.body,
+                shooting_range: phase.shooting_range,
+                bullet_colors: srgb_colors(&phase.bullet_colors),
+                states: phase.states,
+                teleport: phase.teleport,
+                transition: Some(phase.transition),
+                aseprite: load_context.load(phase.aseprite),
+            });
+        }
+
+        Ok(BossDefinition {
+            name: file.name,
+            life: file.life,
+            phase: file.phase,
+            intro: file.intro,
+            stages,
+            attack_sounds: file
+                .attack_sounds
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect(),
+            damage_sounds: file
+                .damage_sounds
+                .into_iter()
+                .map(|path| load_context.load(path))
+                .collect(),
+            intro_sound: file.intro_sound.map(|path| load_context.load(path)),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["boss.ron"]
+    }
+}
@@ -0,0 +1,138 @@
+//! A single boss that goes through phases as it loses life.
+//! Each time its life drops to the next threshold of its [`BossDefinition`],
+//! the boss takes on the next stage: attacks, moves, sprite and body.
+//! While the transition animation plays it stands still and can't be hurt.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_aseprite_ultra::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        difficulty::Difficulty,
+        level::{
+            boss_definition::{BossDefinition, BossSource},
+            enemies::Enemy,
+            teleport::TeleportAbility,
+        },
+    },
+    utils::cam::CameraShakeState,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (advance_boss_phases, end_phase_transitions)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component, Debug)]
+pub struct PhaseProgress {
+    /// Index of the current stage in [`BossDefinition::stages`]
+    pub current: usize,
+    /// Life at which each next stage starts
+    pub thresholds: Vec<usize>,
+    pub max_life: usize,
+}
+
+impl PhaseProgress {
+    pub fn new(definition: &BossDefinition, difficulty: &Difficulty) -> Self {
+        Self {
+            current: 0,
+            thresholds: definition.thresholds(difficulty),
+            max_life: difficulty.boss_life(definition.life),
+        }
+    }
+}
+
+/// A boss changing phase: it stands still, holds its fire and takes no damage
+#[derive(Component, Debug)]
+pub struct PhaseTransition(pub Timer);
+
+fn advance_boss_phases(
+    mut commands: Commands,
+    definitions: Res<Assets<BossDefinition>>,
+    difficulty: Res<Difficulty>,
+    mut camera_shake_q: Query<&mut CameraShakeState>,
+    mut boss_query: Query<
+        (
+            Entity,
+            &BossSource,
+            &mut PhaseProgress,
+            &mut Enemy,
+            &mut AseAnimation,
+            &mut LinearVelocity,
+        ),
+        Without<PhaseTransition>,
+    >,
+) {
+    for (entity, source, mut progress, mut enemy, mut animation, mut velocity) in &mut boss_query {
+        // At zero the fight is over, not moving on
+        if enemy.life == 0 {
+            continue;
+        }
+        let Some(&at_life) = progress.thresholds.get(progress.current) else {
+            continue;
+        };
+        if enemy.life > at_life {
+            continue;
+        }
+        let Some(definition) = definitions.get(&source.definition) else {
+            continue;
+        };
+        progress.current += 1;
+        let stage = definition.stage(progress.current);
+        info!("{} enters phase {}", definition.name, progress.current + 1);
+
+        enemy.shooting_range = stage.shooting_range;
+        enemy.bullet_colors = stage.bullet_colors.clone();
+        let mut behavior = stage.behavior(source.origin, &difficulty);
+        behavior.enter(0, &mut enemy);
+        *velocity = LinearVelocity::ZERO;
+        animation.aseprite = stage.aseprite.clone();
+
+        let secs = match &stage.transition {
+            Some(transition) => {
+                if let Some(tag) = &transition.animation {
+                    animation
+                        .animation
+                        .play(tag, AnimationRepeat::Count(transition.repeats));
+                    animation.animation.then("Idle", AnimationRepeat::Loop);
+                }
+                if let Ok(mut shake) = camera_shake_q.single_mut() {
+                    shake.trauma = transition.shake;
+                }
+                transition.secs
+            }
+            None => 0.0,
+        };
+
+        let mut boss = commands.entity(entity);
+        boss.insert((
+            behavior,
+            stage.body.locked_axes(),
+            stage.body.rigid_body(),
+            Collider::circle(stage.collider_radius),
+            PhaseTransition(Timer::from_seconds(secs, TimerMode::Once)),
+        ));
+        match &stage.teleport {
+            Some(teleport) => boss.insert(teleport.to_ability(progress.max_life)),
+            None => boss.remove::<TeleportAbility>(),
+        };
+    }
+}
+
+fn end_phase_transitions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut PhaseTransition)>,
+) {
+    for (entity, mut transition) in &mut query {
+        if transition.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<PhaseTransition>();
+        }
+    }
+}
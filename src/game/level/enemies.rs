@@ -1,8 +1,5 @@
 use crate::{
-    game::level::{
-        bosses::{Phase1Assets, Phase2Assets, Phase3Assets},
-        enemy_behavior::{EnemyAttack, Move},
-    },
+    game::level::enemy_behavior::{EnemyAttack, Move},
     screens::gameplay::GameplayLifetime,
 };
 use avian2d::{math::TAU, prelude::*};
@@ -14,20 +11,33 @@ use rand::Rng;
 #[require(GameplayLifetime, Collider)]
 pub struct Enemy {
     pub life: usize,
+    /// Stack of moves, the last one runs first
     pub moves: Vec<Move>,
+    /// Moves to queue again whenever `moves` runs out. Wanders randomly if empty.
+    pub move_pattern: Vec<Move>,
     pub attacks: Vec<EnemyAttack>,
     pub shooting_range: f32,
     pub attack_idx: usize,
+    /// How many attacks ran to the end so far
+    pub attacks_finished: usize,
+    /// Bullet colors, one per attack (wraps around). Kept readable against the map.
+    pub bullet_colors: Vec<Color>,
 }
 
+/// Bullet color for enemies that don't set their own palette
+pub const DEFAULT_BULLET_COLOR: Color = Color::srgb(1.0, 0.45, 0.45);
+
 impl Default for Enemy {
     fn default() -> Self {
         Self {
             life: 1, // GDD "Enemies to have 1-5 lives then maybe?"
             moves: Vec::<Move>::new(),
+            move_pattern: Vec::<Move>::new(),
             attacks: Vec::<EnemyAttack>::new(),
             shooting_range: 100.0,
             attack_idx: 0,
+            attacks_finished: 0,
+            bullet_colors: vec![DEFAULT_BULLET_COLOR],
         }
     }
 }
@@ -40,34 +50,35 @@ impl Enemy {
     pub const RANDOM_MAX_MOVES: usize = 10;
     pub const RANDOM_MIN_MOVES: usize = 2;
 
-    pub fn new_random(life: usize) -> Self {
-        Self {
-            life,
-            moves: Self::get_random_linear_moves(),
-            attacks: Vec::new(),
-            shooting_range: 100.0,
-            attack_idx: 0,
+    pub fn new(life: usize) -> Self {
+        Self { life, ..default() }
+    }
+
+    /// Queue the next round of moves: the pattern if there's one, random ones otherwise
+    pub fn refill_moves(&mut self, rng: &mut impl Rng) {
+        if self.move_pattern.is_empty() {
+            self.random_linear_moves(rng);
+        } else {
+            self.moves.extend(self.move_pattern.iter().rev().cloned());
         }
     }
 
-    pub fn random_linear_moves(&mut self) {
-        self.moves.append(&mut Self::get_random_linear_moves());
+    pub fn random_linear_moves(&mut self, rng: &mut impl Rng) {
+        self.moves.append(&mut Self::get_random_linear_moves(rng));
     }
 
-    pub fn get_random_linear_moves() -> Vec<Move> {
-        let mut rng = rand::rng();
+    pub fn get_random_linear_moves(rng: &mut impl Rng) -> Vec<Move> {
         let n = rng.random_range(Self::RANDOM_MIN_MOVES..=Self::RANDOM_MAX_MOVES);
-        Self::get_random_linear_n_moves(n)
+        Self::get_random_linear_n_moves(n, rng)
     }
 
-    pub fn get_random_linear_n_moves(n: usize) -> Vec<Move> {
+    pub fn get_random_linear_n_moves(n: usize, rng: &mut impl Rng) -> Vec<Move> {
         let mut ms = Vec::<Move>::new();
-        (0..n).for_each(|_| ms.push(Self::get_random_move_unit_velocity()));
+        (0..n).for_each(|_| ms.push(Self::get_random_move_unit_velocity(rng)));
         ms
     }
 
-    pub fn get_random_move_unit_velocity() -> Move {
-        let mut rng = rand::rng();
+    pub fn get_random_move_unit_velocity(rng: &mut impl Rng) -> Move {
         let mag: f32 = rng.random_range(Self::RANDOM_MIN_SPEED..=Self::RANDOM_MAX_SPEED);
         let ang: f32 = rng.random_range(0.0..TAU); // Repetitive bikeshed at the math channel
         Move::UnitVelocity(
@@ -83,21 +94,37 @@ impl Enemy {
         self.attacks.push(attack);
         self
     }
+    pub fn with_move_pattern(mut self, moves: Vec<Move>) -> Self {
+        self.move_pattern = moves;
+        self
+    }
     pub fn with_shooting_range(mut self, range: f32) -> Self {
         self.shooting_range = range;
         self
     }
+    pub fn with_bullet_colors(mut self, colors: Vec<Color>) -> Self {
+        self.bullet_colors = colors;
+        self
+    }
+
+    /// Color of the bullets of the current attack
+    pub fn bullet_color(&self) -> Color {
+        if self.bullet_colors.is_empty() {
+            return DEFAULT_BULLET_COLOR;
+        }
+        self.bullet_colors[self.attack_idx % self.bullet_colors.len()]
+    }
 }
 
 #[derive(Asset, Clone, Reflect)]
 pub struct EnemyAssets {
     pub eye_enemy: EyeEnemyAssets,
-    pub phase1: Phase1Assets,
-    pub phase2: Phase2Assets,
-    pub phase3: Phase3Assets,
     pub bullet: Handle<Image>,
     #[dependency]
     pub throw: Handle<AudioSource>,
+    /// Attack telegraph charge-up
+    #[dependency]
+    pub charge: Handle<AudioSource>,
 }
 
 #[derive(Asset, Clone, Reflect)]
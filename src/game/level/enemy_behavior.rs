@@ -1,22 +1,30 @@
+use std::time::Duration;
+
 use avian2d::{math::TAU, prelude::*};
 use bevy::prelude::*;
 use bevy_aseprite_ultra::prelude::*;
 use rand::{Rng, seq::IndexedRandom};
+use serde::Deserialize;
 
 use crate::{
     PausableSystems,
     audio::sound_effect,
     game::{
         animation::AnimationAssets,
+        difficulty::Difficulty,
         level::{
-            bosses::{
-                Boss, BossIntroPlaying, BossPhase, PHASE_1_NAME, PHASE_2_NAME, PHASE_3_NAME,
-                TUTORIAL_BOSS_NAME,
-            },
+            boss_definition::{BossDefinition, BossSource},
+            boss_phases::PhaseTransition,
+            bosses::{Boss, BossIntroPlaying},
             enemies::Enemy,
-            projectiles::{Hostile, boss_basic_bullet, enemy_basic_bullet},
+            projectiles::{
+                BOSS_BULLET_SPEED, ENEMY_BULLET_SPEED, Hostile, boss_basic_bullet,
+                enemy_basic_bullet,
+            },
+            teleport::{TeleportAbility, Teleporting},
         },
         player::Player,
+        rng::GameRng,
     },
     utils::safe_dir,
 };
@@ -29,7 +37,7 @@ pub(super) fn plugin(app: &mut App) {
             update_moves,
             update_boss_moves,
             enemy_shooting_system,
-            boss_teleport_system,
+            draw_telegraphs,
         )
             .in_set(PausableSystems),
     );
@@ -37,40 +45,43 @@ pub(super) fn plugin(app: &mut App) {
 
 fn update_moves(
     time: Res<Time>,
-    enemy_query: Query<(&mut LinearVelocity, &mut Enemy), Without<Boss>>,
+    mut rng: ResMut<GameRng>,
+    player: Option<Single<&Transform, With<Player>>>,
+    enemy_query: Query<(&mut LinearVelocity, &mut Enemy, &Transform), Without<Boss>>,
 ) {
     let d = time.delta();
-    for (mut velocity, mut enemy) in enemy_query {
-        let mut is_pop = false;
-        if let Some(m) = enemy.moves.last_mut() {
-            match m {
-                Move::UnitVelocity(v, timer) => {
-                    if timer.is_finished() {
-                        is_pop = true;
-                        *velocity = LinearVelocity::ZERO;
-                    } else {
-                        timer.tick(d);
-                        *velocity = *v;
-                    }
-                } //_ => {},
-            }
-        } else {
-            enemy.random_linear_moves(); // refill
-        }
-        enemy.moves.pop_if(|_| is_pop);
+    let player_pos = player.map(|t| t.translation.xy());
+    for (mut velocity, mut enemy, transform) in enemy_query {
+        step_moves(
+            &mut enemy,
+            &mut velocity,
+            d,
+            transform.translation.xy(),
+            player_pos,
+            rng.gameplay(),
+        );
     }
 }
 
 fn update_boss_moves(
     time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    player: Option<Single<&Transform, With<Player>>>,
     enemy_query: Query<
-        (&mut LinearVelocity, &mut Enemy, &BossPhase),
-        (With<Boss>, Without<BossIntroPlaying>),
+        (&mut LinearVelocity, &mut Enemy, &Transform),
+        (
+            With<Boss>,
+            Without<BossIntroPlaying>,
+            Without<PhaseTransition>,
+            Without<Teleporting>,
+        ),
     >,
 ) {
     let d = time.delta();
-    for (mut velocity, mut enemy, phase) in enemy_query {
-        if phase.current_phase == 1 {
+    let player_pos = player.map(|t| t.translation.xy());
+    for (mut velocity, mut enemy, transform) in enemy_query {
+        // Bosses only move the way they are told to
+        if enemy.move_pattern.is_empty() {
             if !velocity.eq(&LinearVelocity::ZERO) {
                 *velocity = LinearVelocity::ZERO;
             }
@@ -79,33 +90,43 @@ fn update_boss_moves(
             }
             continue;
         }
-        let mut is_pop = false;
-        if let Some(m) = enemy.moves.last_mut() {
-            match m {
-                Move::UnitVelocity(v, timer) => {
-                    if timer.is_finished() {
-                        is_pop = true;
-                        *velocity = LinearVelocity::ZERO;
-                    } else {
-                        timer.tick(d);
-                        if phase.current_phase == 2 {
-                            velocity.x = v.x;
-                            velocity.y = 0.0;
-                        } else {
-                            *velocity = *v;
-                        }
-                    }
-                } //_ => {},
-            }
-        } else {
-            enemy.random_linear_moves(); // refill
+        step_moves(
+            &mut enemy,
+            &mut velocity,
+            d,
+            transform.translation.xy(),
+            player_pos,
+            rng.gameplay(),
+        );
+    }
+}
+
+/// Runs the move on top of the stack and pops it once it's over
+fn step_moves(
+    enemy: &mut Enemy,
+    velocity: &mut LinearVelocity,
+    d: Duration,
+    position: Vec2,
+    player: Option<Vec2>,
+    rng: &mut impl Rng,
+) {
+    let Some(m) = enemy.moves.last_mut() else {
+        enemy.refill_moves(rng);
+        return;
+    };
+    match m.step(d, position, player, rng) {
+        Some(v) => velocity.0 = v,
+        None => {
+            *velocity = LinearVelocity::ZERO;
+            enemy.moves.pop();
         }
-        enemy.moves.pop_if(|_| is_pop);
     }
 }
 
 fn check_enemy_death(
-    mut enemy_query: Query<(Entity, &Enemy, &mut AseAnimation), Without<Boss>>,
+    // Collider is gone once dying, so this runs once per enemy
+    mut enemy_query: Query<(Entity, &Enemy, &mut AseAnimation), (Without<Boss>, With<Collider>)>,
+    dying_query: Query<(), With<Enemy>>,
     mut events: MessageReader<AnimationEvents>,
     mut cmd: Commands,
 ) {
@@ -122,8 +143,10 @@ fn check_enemy_death(
     }
     for event in events.read() {
         match event {
-            AnimationEvents::Finished(entity) => cmd.entity(*entity).despawn(),
-            AnimationEvents::LoopCycleFinished(_entity) => (),
+            AnimationEvents::Finished(entity) if dying_query.contains(*entity) => {
+                cmd.entity(*entity).despawn()
+            }
+            _ => (),
         };
     }
 }
@@ -132,19 +155,33 @@ fn check_enemy_death(
 fn enemy_shooting_system(
     mut cmd: Commands,
     time: Res<Time>,
-    player_query: Query<&Transform, With<Player>>,
+    mut rng: ResMut<GameRng>,
+    player_query: Query<(&Transform, &LinearVelocity), With<Player>>,
     mut enemy_query: Query<
-        (&Transform, &mut Enemy, Has<Boss>, Option<&Name>),
-        (Without<Player>, Without<BossIntroPlaying>),
+        (
+            &Transform,
+            &mut Enemy,
+            Has<Boss>,
+            Option<&BossSource>,
+            Option<&mut AseAnimation>,
+        ),
+        (
+            Without<Player>,
+            Without<BossIntroPlaying>,
+            Without<PhaseTransition>,
+            Without<Teleporting>,
+        ),
     >,
     anim_assets: If<Res<AnimationAssets>>,
+    definitions: Res<Assets<BossDefinition>>,
+    difficulty: Res<Difficulty>,
 ) {
-    let Ok(player_transform) = player_query.single() else {
+    let Ok((player_transform, player_velocity)) = player_query.single() else {
         return; // No player, don't shoot
     };
     let player_pos = player_transform.translation.xy();
-    for (enemy_transform, mut shooter, is_boss, o_name) in enemy_query.iter_mut() {
-        if shooter.attacks.is_empty() {
+    for (enemy_transform, mut shooter, is_boss, o_source, animation) in enemy_query.iter_mut() {
+        if shooter.attacks.is_empty() || (!is_boss && shooter.life == 0) {
             continue;
         }
         let enemy_pos = enemy_transform.translation.xy();
@@ -152,11 +189,31 @@ fn enemy_shooting_system(
         if distance_to_player <= shooter.shooting_range {
             let idx = shooter.attack_idx % shooter.attacks.len();
             let current_attack = &mut shooter.attacks[idx];
+            // warn first, the attack starts once the telegraph is over
+            if let Some(telegraph) = current_attack.telegraph.as_mut()
+                && !telegraph.lead.is_finished()
+            {
+                if telegraph.lead.elapsed().is_zero() {
+                    if let (Some(tag), Some(mut animation)) = (&telegraph.animation, animation) {
+                        animation.animation.play(tag, AnimationRepeat::Count(1));
+                        animation.animation.then("Idle", AnimationRepeat::Loop);
+                    }
+                    if telegraph.sound {
+                        cmd.spawn(sound_effect(anim_assets.enemies.charge.clone()));
+                    }
+                }
+                telegraph.lead.tick(time.delta());
+                continue;
+            }
             // check duration of current attack - reset if finished
             current_attack.duration.tick(time.delta());
             if current_attack.duration.is_finished() {
                 current_attack.duration.reset();
+                if let Some(telegraph) = current_attack.telegraph.as_mut() {
+                    telegraph.lead.reset();
+                }
                 shooter.attack_idx = (idx + 1) % shooter.attacks.len();
+                shooter.attacks_finished += 1;
                 continue;
             }
             // shoot on cooldown
@@ -164,63 +221,44 @@ fn enemy_shooting_system(
             if current_attack.cooldown_timer.just_finished() {
                 let enemy_pos = enemy_transform.translation.xy();
                 let enemy_radius = 12.0; // Should match enemy collider radius
-                let dir = (player_pos - enemy_pos).normalize();
+                let bullet_speed = difficulty.bullet_speed(if is_boss {
+                    BOSS_BULLET_SPEED
+                } else {
+                    ENEMY_BULLET_SPEED
+                });
+                let aim = current_attack.aim;
+                let mut dir = aim.direction(enemy_pos, player_pos, player_velocity.0, bullet_speed);
+                if aim.error > 0.0 {
+                    let error = rng.gameplay().random_range(-aim.error..=aim.error);
+                    dir = Vec2::from_angle(dir.to_angle() + error);
+                }
                 let mut directions = Vec::new();
                 for pattern in &current_attack.shooting_pattern {
-                    directions.extend(get_shooting_patterns(dir, pattern, current_attack));
+                    directions.extend(get_shooting_patterns(
+                        dir,
+                        pattern,
+                        current_attack,
+                        &difficulty,
+                        rng.gameplay(),
+                    ));
                 }
+                let bullet_color = shooter.bullet_color();
                 for direction in directions {
-                    // Generate a random color for each bullet
-                    let mut rng = rand::rng();
-                    let random_color = Color::srgb(
-                        rng.random_range(0.3..=1.0),
-                        rng.random_range(0.3..=1.0),
-                        rng.random_range(0.3..=1.0),
-                    );
                     if is_boss {
-                        if let Some(name) = o_name {
-                            match name.as_str() {
-                                TUTORIAL_BOSS_NAME => {
-                                    cmd.spawn(sound_effect(anim_assets.enemies.throw.clone()))
-                                }
-                                PHASE_1_NAME => cmd.spawn(sound_effect(
-                                    anim_assets
-                                        .enemies
-                                        .phase1
-                                        .attacks
-                                        .choose(&mut rand::rng())
-                                        .unwrap()
-                                        .clone(),
-                                )),
-                                PHASE_2_NAME => cmd.spawn(sound_effect(
-                                    anim_assets
-                                        .enemies
-                                        .phase2
-                                        .attacks
-                                        .choose(&mut rand::rng())
-                                        .unwrap()
-                                        .clone(),
-                                )),
-                                PHASE_3_NAME => cmd.spawn(sound_effect(
-                                    anim_assets
-                                        .enemies
-                                        .phase3
-                                        .attacks
-                                        .choose(&mut rand::rng())
-                                        .unwrap()
-                                        .clone(),
-                                )),
-                                _ => {
-                                    panic!("unknown boss")
-                                }
-                            };
-                        }
+                        let sound = o_source
+                            .and_then(|source| definitions.get(&source.definition))
+                            .and_then(|definition| {
+                                definition.attack_sounds.choose(rng.cosmetic()).cloned()
+                            })
+                            .unwrap_or_else(|| anim_assets.enemies.throw.clone());
+                        cmd.spawn(sound_effect(sound));
                         cmd.spawn(boss_basic_bullet::<Hostile>(
                             enemy_pos,
                             direction,
                             enemy_radius,
+                            bullet_speed,
                             &anim_assets,
-                            random_color,
+                            bullet_color,
                         ));
                     } else {
                         cmd.spawn(sound_effect(anim_assets.enemies.throw.clone()));
@@ -228,8 +266,9 @@ fn enemy_shooting_system(
                             enemy_pos,
                             direction,
                             enemy_radius,
+                            bullet_speed,
                             &anim_assets,
-                            random_color,
+                            bullet_color,
                         ));
                     }
                 }
@@ -238,11 +277,93 @@ fn enemy_shooting_system(
     }
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone)]
 pub struct EnemyAttack {
     pub cooldown_timer: Timer,
     pub duration: Timer,
     pub shooting_pattern: Vec<ShootingPattern>,
+    pub aim: Aim,
+    pub telegraph: Option<Telegraph>,
+    /// Teleport before the attack starts, for bosses with a [`TeleportAbility`]
+    pub teleport: bool,
+}
+
+/// Where an attack points its patterns
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Aim {
+    pub mode: AimMode,
+    /// Largest random error either way, in radians
+    pub error: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub enum AimMode {
+    /// Where the player is now
+    #[default]
+    Current,
+    /// Where the player will be when the bullet gets there, if they keep going
+    Intercept,
+    /// In between: 0 is `Current` and 1 is `Intercept`
+    Blend(f32),
+}
+
+impl Aim {
+    /// Direction to shoot from `from`, before the random error
+    pub fn direction(&self, from: Vec2, target: Vec2, target_velocity: Vec2, speed: f32) -> Vec2 {
+        let lead = || intercept(from, target, target_velocity, speed).unwrap_or(target);
+        let point = match self.mode {
+            AimMode::Current => target,
+            AimMode::Intercept => lead(),
+            AimMode::Blend(t) => target.lerp(lead(), t.clamp(0.0, 1.0)),
+        };
+        (point - from).normalize_or(Vec2::NEG_Y)
+    }
+}
+
+/// Where a bullet at `speed` from `from` meets a target moving at `velocity`.
+/// None if it can't catch up.
+fn intercept(from: Vec2, target: Vec2, velocity: Vec2, speed: f32) -> Option<Vec2> {
+    let d = target - from;
+    // |d + velocity * t| = speed * t
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * d.dot(velocity);
+    let c = d.length_squared();
+    let t = if a.abs() < 1.0e-3 {
+        if b.abs() < 1.0e-6 {
+            return None;
+        }
+        -c / b
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+            .into_iter()
+            .filter(|t| *t > 0.0)
+            .reduce(f32::min)?
+    };
+    (t > 0.0).then(|| target + velocity * t)
+}
+
+/// Warning before an attack: a wind-up, lines where it will fire and a
+/// charge-up sound, for `lead` before the first shot
+#[derive(Debug, Clone)]
+pub struct Telegraph {
+    pub lead: Timer,
+    /// Aseprite tag to play as a wind-up
+    pub animation: Option<String>,
+    /// Draw where the patterns will fire
+    pub lines: bool,
+    pub sound: bool,
+}
+
+impl Telegraph {
+    /// Whether the warning is on right now
+    pub fn is_showing(&self) -> bool {
+        !self.lead.elapsed().is_zero() && !self.lead.is_finished()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -255,53 +376,125 @@ pub enum ShootingPattern {
     Sweep { arc: f32, clockwise: bool },
 }
 
-/// Shooting Patterns
-fn get_shooting_patterns(
+/// Longest a telegraph line gets
+const TELEGRAPH_LENGTH: f32 = 160.0;
+/// Lines drawn across the arc of patterns without fixed directions
+const TELEGRAPH_CONE_LINES: usize = 7;
+
+/// Lines where the telegraphed attack will fire, brighter as it gets closer
+fn draw_telegraphs(
+    mut gizmos: Gizmos,
+    player_query: Query<(&Transform, &LinearVelocity), With<Player>>,
+    enemy_query: Query<(&Transform, &Enemy, Has<Boss>), Without<Player>>,
+    difficulty: Res<Difficulty>,
+) {
+    let Ok((player_transform, player_velocity)) = player_query.single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.xy();
+    for (transform, enemy, is_boss) in &enemy_query {
+        if enemy.attacks.is_empty() {
+            continue;
+        }
+        let attack = &enemy.attacks[enemy.attack_idx % enemy.attacks.len()];
+        let Some(telegraph) = &attack.telegraph else {
+            continue;
+        };
+        if !telegraph.lines || !telegraph.is_showing() {
+            continue;
+        }
+        let origin = transform.translation.xy();
+        let bullet_speed = difficulty.bullet_speed(if is_boss {
+            BOSS_BULLET_SPEED
+        } else {
+            ENEMY_BULLET_SPEED
+        });
+        let dir = attack
+            .aim
+            .direction(origin, player_pos, player_velocity.0, bullet_speed);
+        let length = enemy.shooting_range.min(TELEGRAPH_LENGTH);
+        let color = enemy
+            .bullet_color()
+            .with_alpha(0.2 + 0.6 * telegraph.lead.fraction());
+        for pattern in &attack.shooting_pattern {
+            for ray in telegraph_rays(dir, pattern, &difficulty) {
+                gizmos.line_2d(origin, origin + ray * length, color);
+            }
+        }
+    }
+}
+
+/// Where `pattern` will fire. Patterns that pick their angle shot by shot
+/// show the whole cone they can hit.
+fn telegraph_rays(dir: Vec2, pattern: &ShootingPattern, difficulty: &Difficulty) -> Vec<Vec2> {
+    match pattern {
+        ShootingPattern::Random { arc, .. } | ShootingPattern::Sweep { arc, .. } => {
+            let base_angle = dir.to_angle();
+            let step = arc / (TELEGRAPH_CONE_LINES - 1) as f32;
+            (0..TELEGRAPH_CONE_LINES)
+                .map(|i| Vec2::from_angle(base_angle - arc / 2.0 + step * i as f32))
+                .collect()
+        }
+        _ => fixed_directions(dir, pattern, difficulty).unwrap_or_default(),
+    }
+}
+
+/// Directions of the patterns that fire the same way every time, shared by
+/// the shots and their telegraph. `None` for the ones that pick their angle
+/// shot by shot.
+fn fixed_directions(
     dir: Vec2,
     pattern: &ShootingPattern,
-    current_attack: &EnemyAttack,
-) -> Vec<Dir2> {
+    difficulty: &Difficulty,
+) -> Option<Vec<Vec2>> {
     let base_angle = dir.to_angle();
     match pattern {
-        ShootingPattern::Straight => vec![safe_dir(dir)],
+        ShootingPattern::Straight => Some(vec![dir]),
         ShootingPattern::Spread { count, arc } => {
-            if *count <= 1 {
-                return vec![safe_dir(dir)];
+            let count = difficulty.bullet_count(*count);
+            if count <= 1 {
+                return Some(vec![dir]);
             }
-            let mut dirs = Vec::with_capacity(*count);
-            let half_arc = arc / 2.0;
-            // The step size between each bullet
-            let step = arc / (*count as f32 - 1.0);
-
-            for i in 0..*count {
-                // Calculate offset: start from -half_arc and add step
-                let angle_offset = -half_arc + (step * i as f32);
-                let new_dir = Vec2::from_angle(base_angle + angle_offset);
-                dirs.push(safe_dir(new_dir));
-            }
-            dirs
+            // From -arc/2 to +arc/2, evenly apart
+            let step = arc / (count as f32 - 1.0);
+            Some(
+                (0..count)
+                    .map(|i| Vec2::from_angle(base_angle - arc / 2.0 + step * i as f32))
+                    .collect(),
+            )
         }
         ShootingPattern::Ring { count } => {
-            let mut dirs = Vec::with_capacity(*count);
-            let step = TAU / *count as f32;
-
-            for i in 0..*count {
-                let angle = base_angle + (step * i as f32);
-                dirs.push(safe_dir(Vec2::from_angle(angle)));
-            }
-            dirs
-        }
-        ShootingPattern::Flank { angle } => {
-            vec![
-                safe_dir(Vec2::from_angle(base_angle - angle)),
-                safe_dir(Vec2::from_angle(base_angle + angle)),
-            ]
+            let count = difficulty.bullet_count(*count);
+            let step = TAU / count as f32;
+            Some(
+                (0..count)
+                    .map(|i| Vec2::from_angle(base_angle + step * i as f32))
+                    .collect(),
+            )
         }
+        ShootingPattern::Flank { angle } => Some(vec![
+            Vec2::from_angle(base_angle - angle),
+            Vec2::from_angle(base_angle + angle),
+        ]),
+        ShootingPattern::Random { .. } | ShootingPattern::Sweep { .. } => None,
+    }
+}
+
+/// Shooting Patterns
+fn get_shooting_patterns(
+    dir: Vec2,
+    pattern: &ShootingPattern,
+    current_attack: &EnemyAttack,
+    difficulty: &Difficulty,
+    rng: &mut impl Rng,
+) -> Vec<Dir2> {
+    let base_angle = dir.to_angle();
+    match pattern {
         ShootingPattern::Random { count, arc } => {
-            let mut rng = rand::rng();
-            let mut dirs = Vec::with_capacity(*count);
+            let count = difficulty.bullet_count(*count);
+            let mut dirs = Vec::with_capacity(count);
             let half_arc = arc / 2.0;
-            for _ in 0..*count {
+            for _ in 0..count {
                 // Random offset between -half and +half
                 let offset = rng.random_range(-half_arc..=half_arc);
                 dirs.push(safe_dir(Vec2::from_angle(base_angle + offset)));
@@ -335,6 +528,11 @@ fn get_shooting_patterns(
             let sweep_dir = Vec2::from_angle(base_angle + angle_offset);
             vec![safe_dir(sweep_dir)]
         }
+        _ => fixed_directions(dir, pattern, difficulty)
+            .unwrap_or_default()
+            .into_iter()
+            .map(safe_dir)
+            .collect(),
     }
 }
 
@@ -342,27 +540,242 @@ fn get_shooting_patterns(
 #[derive(Clone)]
 pub enum Move {
     UnitVelocity(LinearVelocity, Timer),
-    // UnitWeirdMotion,
-    // UnitDance,
-    // UnitPathfinding,
+    /// Walks through the points in order, done at the last one. A point that
+    /// can't be reached is given up on, see [`WaypointLeg`].
+    FollowWaypoints {
+        points: Vec<Vec2>,
+        speed: f32,
+        current: usize,
+        leg: Option<WaypointLeg>,
+    },
+    /// Circles around `center`, or starts circling from where it is if `None`
+    OrbitPoint {
+        center: Option<Vec2>,
+        radius: f32,
+        angular_speed: f32, // radians per second, counterclockwise if positive
+        timer: Timer,
+    },
+    Chase {
+        target: ChaseTarget,
+        speed: f32,
+        timer: Timer,
+    },
+    /// Drifts along `velocity` while swaying from side to side
+    SineDrift {
+        velocity: Vec2,
+        amplitude: f32,
+        frequency: f32,
+        timer: Timer,
+    },
+    /// Holds still for `wind_up` then rushes in a straight line
+    Dash {
+        toward_player: bool, // random direction otherwise
+        speed: f32,
+        wind_up: Timer,
+        timer: Timer,
+        direction: Option<Vec2>,
+    },
 }
 
-#[derive(Component)]
-pub struct TeleportAbility {
-    pub positions: Vec<Vec2>,
-    pub timer: Timer,
-    pub current_index: usize,
+/// Progress toward the current waypoint. The enemy moves on to the next one
+/// once it stops getting closer for a while, or takes far longer than the
+/// straight walk would.
+#[derive(Clone, Copy, Debug)]
+pub struct WaypointLeg {
+    closest: f32,
+    stalled_secs: f32,
+    secs_left: f32,
 }
 
-fn boss_teleport_system(time: Res<Time>, mut query: Query<(&mut Transform, &mut TeleportAbility)>) {
-    for (mut transform, mut teleport) in query.iter_mut() {
-        teleport.timer.tick(time.delta());
-        if teleport.timer.just_finished() {
-            if !teleport.positions.is_empty() {
-                let next_pos = &teleport.positions[teleport.current_index];
-                transform.translation.x = next_pos.x;
-                transform.translation.y = next_pos.y;
-                teleport.current_index = (teleport.current_index + 1) % teleport.positions.len();
+impl WaypointLeg {
+    /// Seconds without getting closer before giving up on a waypoint
+    pub const STUCK_SECS: f32 = 0.75;
+    /// Time allowed for a leg, relative to walking it in a straight line
+    pub const TIME_SLACK: f32 = 2.0;
+
+    fn new(distance: f32, speed: f32) -> Self {
+        Self {
+            closest: distance,
+            stalled_secs: 0.0,
+            secs_left: distance / speed.max(f32::EPSILON) * Self::TIME_SLACK + Self::STUCK_SECS,
+        }
+    }
+
+    /// Whether the waypoint should be skipped, `distance` away after `secs` more
+    fn is_stuck(&mut self, distance: f32, secs: f32) -> bool {
+        self.secs_left -= secs;
+        // Anything under a pixel is no progress
+        if distance < self.closest - 1.0 {
+            self.closest = distance;
+            self.stalled_secs = 0.0;
+        } else {
+            self.stalled_secs += secs;
+        }
+        self.stalled_secs >= Self::STUCK_SECS || self.secs_left <= 0.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum ChaseTarget {
+    Player,
+}
+
+impl Move {
+    /// Close enough to count as reaching a waypoint
+    pub const WAYPOINT_REACHED: f32 = 4.0;
+    /// How hard an orbit pulls back onto its circle
+    pub const ORBIT_PULL: f32 = 2.0;
+
+    pub fn waypoints(points: Vec<Vec2>, speed: f32) -> Self {
+        Self::FollowWaypoints {
+            points,
+            speed,
+            current: 0,
+            leg: None,
+        }
+    }
+
+    pub fn orbit(radius: f32, angular_speed: f32, secs: f32) -> Self {
+        Self::OrbitPoint {
+            center: None,
+            radius,
+            angular_speed,
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+        }
+    }
+
+    pub fn chase_player(speed: f32, secs: f32) -> Self {
+        Self::Chase {
+            target: ChaseTarget::Player,
+            speed,
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+        }
+    }
+
+    pub fn sine_drift(velocity: Vec2, amplitude: f32, frequency: f32, secs: f32) -> Self {
+        Self::SineDrift {
+            velocity,
+            amplitude,
+            frequency,
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+        }
+    }
+
+    pub fn dash(toward_player: bool, speed: f32, wind_up_secs: f32, secs: f32) -> Self {
+        Self::Dash {
+            toward_player,
+            speed,
+            wind_up: Timer::from_seconds(wind_up_secs, TimerMode::Once),
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+            direction: None,
+        }
+    }
+
+    /// Velocity for this frame, `None` once the move is over
+    pub fn step(
+        &mut self,
+        d: Duration,
+        position: Vec2,
+        player: Option<Vec2>,
+        rng: &mut impl Rng,
+    ) -> Option<Vec2> {
+        match self {
+            Move::UnitVelocity(v, timer) => {
+                if timer.is_finished() {
+                    return None;
+                }
+                timer.tick(d);
+                Some(v.0)
+            }
+            Move::FollowWaypoints {
+                points,
+                speed,
+                current,
+                leg,
+            } => {
+                let point = points.get(*current)?;
+                let to_point = *point - position;
+                let distance = to_point.length();
+                let is_stuck = leg
+                    .get_or_insert_with(|| WaypointLeg::new(distance, *speed))
+                    .is_stuck(distance, d.as_secs_f32());
+                if distance <= Self::WAYPOINT_REACHED || is_stuck {
+                    *current += 1;
+                    *leg = None;
+                    return Some(Vec2::ZERO);
+                }
+                Some(to_point.normalize_or_zero() * *speed)
+            }
+            Move::OrbitPoint {
+                center,
+                radius,
+                angular_speed,
+                timer,
+            } => {
+                if timer.is_finished() {
+                    return None;
+                }
+                timer.tick(d);
+                let center = *center.get_or_insert(position - Vec2::X * *radius);
+                let offset = position - center;
+                let out = offset.normalize_or(Vec2::X);
+                // Go around, and drift back onto the circle if pushed off it
+                let around = out.perp() * *angular_speed * *radius;
+                let back = out * (*radius - offset.length()) * Self::ORBIT_PULL;
+                Some(around + back)
+            }
+            Move::Chase {
+                target,
+                speed,
+                timer,
+            } => {
+                if timer.is_finished() {
+                    return None;
+                }
+                timer.tick(d);
+                let target_pos = match target {
+                    ChaseTarget::Player => player,
+                };
+                // Lost the target: wait for the timer
+                Some(target_pos.map_or(Vec2::ZERO, |p| (p - position).normalize_or_zero() * *speed))
+            }
+            Move::SineDrift {
+                velocity,
+                amplitude,
+                frequency,
+                timer,
+            } => {
+                if timer.is_finished() {
+                    return None;
+                }
+                timer.tick(d);
+                let w = TAU * *frequency;
+                let t = timer.elapsed_secs();
+                let side = velocity.perp().normalize_or_zero();
+                // Derivative of amplitude * sin(w t) so the sway peaks at `amplitude`
+                Some(*velocity + side * *amplitude * w * (w * t).cos())
+            }
+            Move::Dash {
+                toward_player,
+                speed,
+                wind_up,
+                timer,
+                direction,
+            } => {
+                if !wind_up.is_finished() {
+                    wind_up.tick(d);
+                    return Some(Vec2::ZERO);
+                }
+                if timer.is_finished() {
+                    return None;
+                }
+                timer.tick(d);
+                // Aim once, at the end of the wind-up
+                let dir = *direction.get_or_insert_with(|| match (*toward_player, player) {
+                    (true, Some(p)) => (p - position).normalize_or(Vec2::X),
+                    _ => Vec2::from_angle(rng.random_range(0.0..TAU)),
+                });
+                Some(dir * *speed)
             }
         }
     }
@@ -1,20 +1,29 @@
+pub mod behavior;
+pub mod boss_definition;
+pub mod boss_phases;
 pub mod bosses;
 pub mod enemies;
 pub mod enemy_behavior;
+pub mod pickups;
+pub mod projectile_fx;
 pub mod projectiles;
+pub mod teleport;
+pub mod waves;
 
 use avian2d::prelude::{Physics, PhysicsTime};
 use bevy::{prelude::*, state::state::FreelyMutableState};
 use bevy_aseprite_ultra::prelude::{AnimationRepeat, AseAnimation};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     asset_tracking::LoadResource,
     audio::{music, sound_effect},
     game::{
         animation::AnimationAssets,
-        level::bosses::{
-            Boss, BossIntroPlaying, BossIntroTimer, phase1_boss, phase2_boss, phase3_boss,
-            tutorial_boss,
+        difficulty::Difficulty,
+        level::{
+            boss_definition::{BossDefinition, BossDefinitions, BossSource, spawn_boss},
+            bosses::{Boss, BossFlow, BossIntroPlaying, BossIntroTimer},
         },
         player::{PLAYER_Z_TRANSLATION, player},
     },
@@ -33,7 +42,18 @@ use crate::{
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<LevelAssets>()
         .init_state::<Level>()
-        .add_plugins((enemy_behavior::plugin, projectiles::plugin));
+        .init_resource::<BossFlow>()
+        .add_plugins((
+            behavior::plugin,
+            boss_definition::plugin,
+            boss_phases::plugin,
+            enemy_behavior::plugin,
+            pickups::plugin,
+            projectile_fx::plugin,
+            projectiles::plugin,
+            teleport::plugin,
+            waves::plugin,
+        ));
     app.add_systems(
         Update,
         (level_intro, remove_boss_intro_after_delay).run_if(in_state(Screen::Gameplay)),
@@ -44,7 +64,20 @@ pub(super) fn plugin(app: &mut App) {
 /// [`Level`] exists in both [`Screen::Gameplay`] and [`Screen::Loading`]
 /// When a condition meets at [`screens::gameplay::check_boss_and_player`],
 /// The next is level is set, and screen is set [`Screen::Loading`].
-#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, Reflect)]
+#[derive(
+    Clone,
+    Copy,
+    Default,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Debug,
+    Hash,
+    Serialize,
+    Deserialize,
+    Reflect,
+)]
 pub enum Level {
     #[default]
     Tutorial,
@@ -73,6 +106,17 @@ impl FreelyMutableState for Level {}
 
 impl Level {
     pub const LAST_LEVEL: Level = Level::Phase3;
+    pub const ALL: [Level; 4] = [Level::Tutorial, Level::Phase1, Level::Phase2, Level::Phase3];
+
+    pub fn label(&self) -> &'static str {
+        use Level::*;
+        match self {
+            Tutorial => "Tutorial",
+            Phase1 => "Phase 1",
+            Phase2 => "Phase 2",
+            Phase3 => "Phase 3",
+        }
+    }
 
     pub fn next(&self) -> Self {
         use Level::*;
@@ -135,14 +179,16 @@ impl FromWorld for LevelAssets {
 pub fn sfx_intro(
     mut commands: Commands,
     current_level: Res<State<Level>>,
-    anim_assets: Res<AnimationAssets>,
+    flow: Res<BossFlow>,
+    boss_definitions: Res<BossDefinitions>,
+    definitions: Res<Assets<BossDefinition>>,
 ) {
-    use Level::*;
-    match current_level.get() {
-        Phase2 => {
-            commands.spawn(sound_effect(anim_assets.enemies.phase2.intro.clone()));
-        }
-        _ => {}
+    let handle = boss_definitions.for_level(*current_level.get(), *flow);
+    if let Some(sound) = definitions
+        .get(handle)
+        .and_then(|definition| definition.intro_sound.clone())
+    {
+        commands.spawn(sound_effect(sound));
     }
 }
 
@@ -155,8 +201,13 @@ pub fn spawn_level(
     mut commands: Commands,
     level_assets: Res<LevelAssets>,
     anim_assets: Res<AnimationAssets>,
+    flow: Res<BossFlow>,
+    boss_definitions: Res<BossDefinitions>,
+    definitions: Res<Assets<BossDefinition>>,
+    difficulty: Res<Difficulty>,
     mut time: ResMut<Time<Physics>>,
 ) {
+    let stats = difficulty.player_stats(current_level.player_stats());
     let lev_entity = commands
         .spawn((
             Name::new("Level"),
@@ -184,13 +235,7 @@ pub fn spawn_level(
             );
             let player_initial_transform = Vec2::new(0.0, -90.0);
             commands.entity(lev_entity).insert((children![
-                player(
-                    100.0,
-                    &anim_assets,
-                    player_initial_transform,
-                    current_level.player_stats()
-                ),
-                tutorial_boss((0.0, 60.).into(), &anim_assets),
+                player(100.0, &anim_assets, player_initial_transform, stats),
                 (
                     Name::new("Gameplay Music"),
                     DespawnOnExit(Menu::None), // To remove at ending such as to [`Menu::Credit`]
@@ -214,13 +259,7 @@ pub fn spawn_level(
         Phase1 => {
             let player_initial_transform = Vec2::new(0.0, -90.0);
             commands.entity(lev_entity).insert((children![
-                player(
-                    100.0,
-                    &anim_assets,
-                    player_initial_transform,
-                    current_level.player_stats()
-                ),
-                phase1_boss((0.0, 60.0).into(), &anim_assets),
+                player(100.0, &anim_assets, player_initial_transform, stats),
                 (
                     Name::new("Gameplay Music"),
                     DespawnOnExit(Menu::None), // To remove at ending such as to [`Menu::Credit`]
@@ -231,13 +270,7 @@ pub fn spawn_level(
         Phase2 => {
             let player_initial_transform = Vec2::new(0.0, -150.0);
             commands.entity(lev_entity).insert((children![
-                player(
-                    100.0,
-                    &anim_assets,
-                    player_initial_transform,
-                    current_level.player_stats()
-                ),
-                phase2_boss((0.0, 80.0).into(), &anim_assets),
+                player(100.0, &anim_assets, player_initial_transform, stats),
                 (
                     Name::new("Gameplay Music"),
                     DespawnOnExit(Menu::None),
@@ -248,13 +281,7 @@ pub fn spawn_level(
         Phase3 => {
             let player_initial_transform = Vec2::new(0.0, -135.0);
             commands.entity(lev_entity).insert((children![
-                player(
-                    100.0,
-                    &anim_assets,
-                    player_initial_transform,
-                    current_level.player_stats()
-                ),
-                phase3_boss((0., 90.).into(), &anim_assets),
+                player(100.0, &anim_assets, player_initial_transform, stats),
                 (
                     Name::new("Gameplay Music"),
                     DespawnOnExit(Menu::None),
@@ -263,6 +290,21 @@ pub fn spawn_level(
             ],));
         }
     }
+    let boss_xy = match current_level.get() {
+        Tutorial | Phase1 => Vec2::new(0.0, 60.0),
+        Phase2 => Vec2::new(0.0, 80.0),
+        Phase3 => Vec2::new(0.0, 90.0),
+    };
+    let handle = boss_definitions.for_level(*current_level.get(), *flow);
+    if let Some(definition) = definitions.get(handle) {
+        spawn_boss(&mut commands, handle, definition, boss_xy, &difficulty)
+            .insert(ChildOf(lev_entity));
+    } else {
+        error!(
+            "Boss definition for {:?} is not loaded",
+            current_level.get()
+        );
+    }
     commands.spawn((
         Name::new("Level Transition Overlay"),
         LevelEntryOverlay,
@@ -285,25 +327,31 @@ pub fn spawn_level(
 fn level_intro(
     mut cmd: Commands,
     load_q: Query<(Entity, &LoadingFadeInOut), With<LevelEntryOverlay>>,
-    current_level: Res<State<Level>>,
-    mut boss_q: Query<(Entity, &mut AseAnimation), With<Boss>>,
+    mut boss_q: Query<(Entity, &mut AseAnimation, &BossSource), With<Boss>>,
+    definitions: Res<Assets<BossDefinition>>,
     mut camera_shake_q: Query<&mut CameraShakeState>,
 ) {
     for (entity, anim) in &load_q {
         if anim.t >= anim.total_duration {
             cmd.entity(entity).despawn();
-            if current_level.get() != &Level::Tutorial {
-                if let Ok((boss_entity, mut boss_anim)) = boss_q.single_mut() {
-                    boss_anim
-                        .animation
-                        .play("Scream", AnimationRepeat::Count(4));
-                    boss_anim.animation.then("Idle", AnimationRepeat::Loop);
-                    if let Ok(mut shake) = camera_shake_q.single_mut() {
-                        shake.trauma = 2.0;
-                    }
-                    cmd.entity(boss_entity)
-                        .insert(BossIntroTimer(Timer::from_seconds(3.0, TimerMode::Once)));
+            if let Ok((boss_entity, mut boss_anim, source)) = boss_q.single_mut()
+                && let Some(definition) = definitions.get(&source.definition)
+                // Without an intro animation, the dialogue lets the boss go
+                && let Some(tag) = &definition.intro.animation
+            {
+                let intro = &definition.intro;
+                boss_anim
+                    .animation
+                    .play(tag, AnimationRepeat::Count(intro.repeats));
+                boss_anim.animation.then("Idle", AnimationRepeat::Loop);
+                if let Ok(mut shake) = camera_shake_q.single_mut() {
+                    shake.trauma = intro.shake;
                 }
+                cmd.entity(boss_entity)
+                    .insert(BossIntroTimer(Timer::from_seconds(
+                        intro.secs,
+                        TimerMode::Once,
+                    )));
             }
         }
     }
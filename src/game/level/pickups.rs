@@ -0,0 +1,451 @@
+//! Loot lying on the floor: hearts, ammo, coins and a haste buff.
+//! Minions and breakable props roll their [`DropTable`] when they go down.
+//! A pickup drifts to the player once close enough, is collected on touch,
+//! and blinks out if it is left lying for too long.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::{Rng, seq::IndexedRandom};
+use serde::Deserialize;
+
+use crate::{
+    PausableSystems,
+    asset_tracking::LoadResource,
+    audio::sound_effect,
+    game::{
+        animation::AnimationAssets,
+        level::{
+            Level,
+            bosses::BossFlow,
+            enemies::Enemy,
+            projectiles::{Friendly, Projectile},
+            waves::{LevelWaves, WaveDefinition},
+        },
+        player::{PLAYER_Z_TRANSLATION, Player},
+        rng::GameRng,
+    },
+    screens::{Screen, gameplay::GameplayLifetime},
+    utils::collisions_layers::pickup_layers,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<PickupAssets>();
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_breakables);
+    app.add_systems(
+        Update,
+        (
+            drop_enemy_loot,
+            attract_pickups,
+            expire_pickups,
+            animate_frames,
+        )
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        FixedUpdate,
+        (collect_pickups, break_breakables).in_set(PausableSystems),
+    );
+}
+
+/// Player has to be this close for a pickup to start drifting over
+const MAGNET_RADIUS: f32 = 56.0;
+const MAGNET_SPEED: f32 = 180.0;
+const PICKUP_RADIUS: f32 = 6.0;
+/// Seconds a pickup stays on the floor
+const PICKUP_LIFETIME_SECS: f32 = 10.0;
+/// It blinks for the last few of them
+const PICKUP_BLINK_SECS: f32 = 3.0;
+/// How far apart the pickups of one drop land
+const DROP_SCATTER: f32 = 10.0;
+const HASTE_SECS: f32 = 6.0;
+const BLUE_COIN_VALUE: usize = 5;
+const FRAME_SECS: f32 = 0.1;
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PickupAssets {
+    #[dependency]
+    pub potions: Handle<Image>,
+    #[dependency]
+    pub coin: Handle<Image>,
+    #[dependency]
+    pub blue_coin: Handle<Image>,
+    #[dependency]
+    pub vase: Handle<Image>,
+    #[dependency]
+    pub collect: Handle<AudioSource>,
+    /// 3x2 potions of 16x16
+    pub potion_layout: Handle<TextureAtlasLayout>,
+    /// A spinning coin on the first row, 8 frames of 16x16
+    pub coin_layout: Handle<TextureAtlasLayout>,
+    /// 16 frames of 16x16
+    pub vase_layout: Handle<TextureAtlasLayout>,
+}
+
+impl FromWorld for PickupAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut layouts = world.resource_mut::<Assets<TextureAtlasLayout>>();
+        let potion_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(16),
+            3,
+            2,
+            None,
+            None,
+        ));
+        let coin_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(16),
+            8,
+            2,
+            None,
+            None,
+        ));
+        let vase_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(16),
+            16,
+            1,
+            None,
+            None,
+        ));
+        let assets = world.resource::<AssetServer>();
+        Self {
+            potions: assets.load("textures/props/DungeonGathering_Potions.png"),
+            coin: assets.load("textures/props/DungeonGathering_Coin-Sheet.png"),
+            blue_coin: assets.load("textures/props/DungeonGathering_BlueCoin-Sheet.png"),
+            vase: assets.load("textures/props/DungeonGathering_Vase-Shine-Anim.png"),
+            collect: assets.load("audio/sound_effects/player/pickup.ogg"),
+            potion_layout,
+            coin_layout,
+            vase_layout,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PickupKind {
+    Heart,
+    Ammo,
+    Coin,
+    BlueCoin,
+    /// Shorter throw cool time for a while
+    Haste,
+}
+
+impl PickupKind {
+    /// What collecting it does to the player
+    pub fn apply(self, player: &mut Player) {
+        match self {
+            PickupKind::Heart => player.heal(1),
+            PickupKind::Ammo => player.increment_ammo(1),
+            PickupKind::Coin => player.add_coins(1),
+            PickupKind::BlueCoin => player.add_coins(BLUE_COIN_VALUE),
+            PickupKind::Haste => player.hasten(HASTE_SECS),
+        }
+    }
+
+    /// The sprite and how many frames it loops through
+    fn sprite(self, assets: &PickupAssets) -> (Sprite, usize) {
+        let potion = |index| {
+            Sprite::from_atlas_image(
+                assets.potions.clone(),
+                TextureAtlas {
+                    layout: assets.potion_layout.clone(),
+                    index,
+                },
+            )
+        };
+        let coin = |image: &Handle<Image>| {
+            Sprite::from_atlas_image(
+                image.clone(),
+                TextureAtlas {
+                    layout: assets.coin_layout.clone(),
+                    index: 0,
+                },
+            )
+        };
+        match self {
+            PickupKind::Heart => (potion(0), 1),
+            PickupKind::Ammo => (potion(1), 1),
+            PickupKind::Haste => (potion(2), 1),
+            PickupKind::Coin => (coin(&assets.coin), 8),
+            PickupKind::BlueCoin => (coin(&assets.blue_coin), 8),
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+#[require(GameplayLifetime)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    pub lifetime: Timer,
+}
+
+/// What something leaves behind. Each roll picks one entry by weight.
+#[derive(Component, Deserialize, Clone, Debug, Default)]
+pub struct DropTable {
+    #[serde(default = "one_roll")]
+    pub rolls: usize,
+    pub entries: Vec<DropEntry>,
+}
+
+fn one_roll() -> usize {
+    1
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DropEntry {
+    /// `None` drops nothing
+    pub kind: Option<PickupKind>,
+    pub weight: u32,
+}
+
+impl DropTable {
+    pub fn roll(&self, rng: &mut impl Rng) -> Vec<PickupKind> {
+        (0..self.rolls)
+            .filter_map(|_| {
+                self.entries
+                    .choose_weighted(rng, |entry| entry.weight)
+                    .ok()
+                    .and_then(|entry| entry.kind)
+            })
+            .collect()
+    }
+}
+
+/// A prop that breaks after a few hits from the player
+#[derive(Component, Debug)]
+#[require(GameplayLifetime)]
+pub struct Breakable {
+    pub life: usize,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BreakableDef {
+    /// Map coordinates
+    pub position: [f32; 2],
+    pub life: usize,
+    #[serde(default)]
+    pub drops: DropTable,
+}
+
+/// Loops through the first `frames` frames of a texture atlas. Still with one.
+#[derive(Component, Debug)]
+struct FrameAnimation {
+    frames: usize,
+    timer: Timer,
+}
+
+impl FrameAnimation {
+    fn new(frames: usize) -> Self {
+        Self {
+            frames,
+            timer: Timer::from_seconds(FRAME_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+pub fn pickup(kind: PickupKind, xy: Vec2, assets: &PickupAssets) -> impl Bundle {
+    let (sprite, frames) = kind.sprite(assets);
+    (
+        Name::new(format!("{kind:?} Pickup")),
+        Pickup {
+            kind,
+            lifetime: Timer::from_seconds(PICKUP_LIFETIME_SECS, TimerMode::Once),
+        },
+        sprite,
+        FrameAnimation::new(frames),
+        Transform::from_xyz(xy.x, xy.y, PLAYER_Z_TRANSLATION - 1.0),
+        // Dynamic, so the sensor still meets the kinematic player
+        RigidBody::Dynamic,
+        GravityScale(0.0),
+        Collider::circle(PICKUP_RADIUS),
+        Sensor,
+        CollisionEventsEnabled,
+        pickup_layers(),
+    )
+}
+
+fn spawn_drops(
+    commands: &mut Commands,
+    table: &DropTable,
+    xy: Vec2,
+    rng: &mut GameRng,
+    assets: &PickupAssets,
+) {
+    for kind in table.roll(rng.gameplay()) {
+        let offset = Vec2::new(
+            rng.cosmetic().random_range(-DROP_SCATTER..=DROP_SCATTER),
+            rng.cosmetic().random_range(-DROP_SCATTER..=DROP_SCATTER),
+        );
+        commands.spawn(pickup(kind, xy + offset, assets));
+    }
+}
+
+fn drop_enemy_loot(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    assets: If<Res<PickupAssets>>,
+    query: Query<(Entity, &Enemy, &DropTable, &Transform)>,
+) {
+    for (entity, enemy, table, transform) in &query {
+        if enemy.life > 0 {
+            continue;
+        }
+        spawn_drops(
+            &mut commands,
+            table,
+            transform.translation.xy(),
+            &mut rng,
+            &assets,
+        );
+        // Only once
+        commands.entity(entity).remove::<DropTable>();
+    }
+}
+
+fn attract_pickups(
+    player: Option<Single<&Transform, With<Player>>>,
+    mut query: Query<(&Transform, &mut LinearVelocity), With<Pickup>>,
+) {
+    let player_pos = player.map(|t| t.translation.xy());
+    for (transform, mut velocity) in &mut query {
+        let to_player = player_pos.map(|p| p - transform.translation.xy());
+        velocity.0 = match to_player {
+            Some(v) if v.length() <= MAGNET_RADIUS => v.normalize_or_zero() * MAGNET_SPEED,
+            _ => Vec2::ZERO,
+        };
+    }
+}
+
+fn expire_pickups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Pickup, &mut Sprite)>,
+) {
+    for (entity, mut pickup, mut sprite) in &mut query {
+        pickup.lifetime.tick(time.delta());
+        if pickup.lifetime.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let left = pickup.lifetime.remaining_secs();
+        let visible = left > PICKUP_BLINK_SECS || (left * 8.0).fract() < 0.5;
+        sprite.color.set_alpha(if visible { 1.0 } else { 0.2 });
+    }
+}
+
+fn animate_frames(time: Res<Time>, mut query: Query<(&mut FrameAnimation, &mut Sprite)>) {
+    for (mut frames, mut sprite) in &mut query {
+        if frames.frames <= 1 || !frames.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+        if let Some(atlas) = sprite.texture_atlas.as_mut() {
+            atlas.index = (atlas.index + 1) % frames.frames;
+        }
+    }
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut collision_reader: MessageReader<CollisionStart>,
+    assets: If<Res<PickupAssets>>,
+    mut player: Query<&mut Player>,
+    pickups: Query<&Pickup>,
+) {
+    for msg in collision_reader.read() {
+        let (c1, c2) = (msg.collider1, msg.collider2);
+        let (pickup_entity, player_entity) = if pickups.contains(c1) {
+            (c1, c2)
+        } else {
+            (c2, c1)
+        };
+        let (Ok(pickup), Ok(mut player)) =
+            (pickups.get(pickup_entity), player.get_mut(player_entity))
+        else {
+            continue;
+        };
+        pickup.kind.apply(&mut player);
+        commands.spawn(sound_effect(assets.collect.clone()));
+        commands.entity(pickup_entity).despawn();
+    }
+}
+
+fn break_breakables(
+    mut commands: Commands,
+    mut collision_reader: MessageReader<CollisionStart>,
+    mut rng: ResMut<GameRng>,
+    assets: If<Res<PickupAssets>>,
+    anim_assets: If<Res<AnimationAssets>>,
+    mut breakables: Query<(&mut Breakable, &Transform, Option<&DropTable>)>,
+    projectiles: Query<(), (With<Projectile>, With<Friendly>)>,
+) {
+    for msg in collision_reader.read() {
+        let (c1, c2) = (msg.collider1, msg.collider2);
+        let (prop, projectile) = if breakables.contains(c1) {
+            (c1, c2)
+        } else {
+            (c2, c1)
+        };
+        if !projectiles.contains(projectile) {
+            continue;
+        }
+        let Ok((mut breakable, transform, table)) = breakables.get_mut(prop) else {
+            continue;
+        };
+        if breakable.life == 0 {
+            continue;
+        }
+        breakable.life -= 1;
+        if breakable.life > 0 {
+            continue;
+        }
+        if let Some(sound) = anim_assets.projectiles.ricochet.choose(rng.cosmetic()) {
+            commands.spawn(sound_effect(sound.clone()));
+        }
+        if let Some(table) = table {
+            spawn_drops(
+                &mut commands,
+                table,
+                transform.translation.xy(),
+                &mut rng,
+                &assets,
+            );
+        }
+        commands.entity(prop).despawn();
+    }
+}
+
+/// Breakable props are placed by the wave definition of the level
+fn spawn_breakables(
+    mut commands: Commands,
+    current_level: Res<State<Level>>,
+    flow: Res<BossFlow>,
+    level_waves: Res<LevelWaves>,
+    definitions: Res<Assets<WaveDefinition>>,
+    assets: Res<PickupAssets>,
+) {
+    let Some(definition) = level_waves
+        .for_level(*current_level.get(), *flow)
+        .and_then(|handle| definitions.get(handle))
+    else {
+        return;
+    };
+    for def in &definition.breakables {
+        commands.spawn((
+            Name::new("Vase"),
+            Breakable { life: def.life },
+            def.drops.clone(),
+            Sprite::from_atlas_image(
+                assets.vase.clone(),
+                TextureAtlas {
+                    layout: assets.vase_layout.clone(),
+                    index: 0,
+                },
+            ),
+            FrameAnimation::new(16),
+            Transform::from_xyz(def.position[0], def.position[1], PLAYER_Z_TRANSLATION - 1.0),
+            RigidBody::Static,
+            Collider::rectangle(10.0, 14.0),
+            CollisionEventsEnabled,
+        ));
+    }
+}
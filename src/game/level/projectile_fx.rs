@@ -0,0 +1,226 @@
+//! Projectile looks beside the sprite itself: a flash where it appears, a burst
+//! where it hits or runs out and a trail of fading afterimages.
+//! Each projectile bundle declares its own [`ProjectileFx`]. Flashes and bursts
+//! are tags of `projectile-fx.aseprite`, drawn in white and tinted per type.
+
+use bevy::prelude::*;
+use bevy_aseprite_ultra::prelude::{Animation, AnimationEvents, AnimationRepeat, AseAnimation};
+
+use crate::{
+    PausableSystems,
+    game::{animation::AnimationAssets, level::projectiles::PROJECTILE_Z_TRANSLATION},
+    screens::gameplay::GameplayLifetime,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(spawn_flash).add_observer(spawn_impact);
+    app.add_systems(
+        Update,
+        (update_trails, update_fades, despawn_finished_bursts)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component, Clone, Debug)]
+pub struct ProjectileFx {
+    pub color: Color,
+    /// Played where the projectile appears
+    pub flash: Option<Burst>,
+    /// Played where it hits something or runs out, see [`Impact`]
+    pub impact: Option<Burst>,
+    pub trail: Option<Trail>,
+}
+
+impl ProjectileFx {
+    pub fn new(color: Color) -> Self {
+        Self {
+            color,
+            flash: None,
+            impact: None,
+            trail: None,
+        }
+    }
+
+    pub fn with_flash(mut self, tag: &'static str, size: f32) -> Self {
+        self.flash = Some(Burst { tag, size });
+        self
+    }
+
+    pub fn with_impact(mut self, tag: &'static str, size: f32) -> Self {
+        self.impact = Some(Burst { tag, size });
+        self
+    }
+
+    pub fn with_trail(mut self, every_secs: f32, fade_secs: f32) -> Self {
+        self.trail = Some(Trail {
+            timer: Timer::from_seconds(every_secs, TimerMode::Repeating),
+            fade_secs,
+        });
+        self
+    }
+}
+
+/// One aseprite tag played once, `size` pixels wide
+#[derive(Clone, Copy, Debug)]
+pub struct Burst {
+    pub tag: &'static str,
+    pub size: f32,
+}
+
+/// Leaves an afterimage of the sprite every tick of `timer`
+#[derive(Clone, Debug)]
+pub struct Trail {
+    pub timer: Timer,
+    pub fade_secs: f32,
+}
+
+/// Fades the sprite out while growing it by `grow`, then despawns
+#[derive(Component, Debug)]
+#[require(GameplayLifetime)]
+pub struct Fade {
+    pub timer: Timer,
+    pub from_alpha: f32,
+    pub grow: f32,
+}
+
+impl Fade {
+    pub fn new(secs: f32, from_alpha: f32, grow: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+            from_alpha,
+            grow,
+        }
+    }
+}
+
+/// A projectile hit something or ran out. Triggered right before it is
+/// despawned, so a projectile cleared away with the level shows nothing.
+#[derive(EntityEvent, Copy, Clone)]
+pub struct Impact {
+    #[event_target]
+    pub entity: Entity,
+}
+
+/// Despawns a projectile that hit something or ran out, with its impact
+pub fn despawn_with_impact(commands: &mut Commands, entity: Entity) {
+    commands.trigger(Impact { entity });
+    commands.entity(entity).despawn();
+}
+
+/// A flash or burst, gone once its animation is
+#[derive(Component, Debug, Default)]
+#[require(GameplayLifetime)]
+struct BurstAnimation;
+
+fn burst(anim_assets: &AnimationAssets, burst: Burst, xy: Vec2, color: Color) -> impl Bundle {
+    (
+        Name::new("Projectile Burst"),
+        BurstAnimation,
+        AseAnimation {
+            animation: Animation::tag(burst.tag).with_repeat(AnimationRepeat::Count(0)),
+            aseprite: anim_assets.projectiles.fx.clone(),
+        },
+        Sprite {
+            custom_size: Some(Vec2::splat(burst.size)),
+            color,
+            ..default()
+        },
+        Transform::from_xyz(xy.x, xy.y, PROJECTILE_Z_TRANSLATION + 1.0),
+    )
+}
+
+fn spawn_flash(
+    add: On<Add, ProjectileFx>,
+    mut commands: Commands,
+    anim_assets: If<Res<AnimationAssets>>,
+    query: Query<(&ProjectileFx, &Transform)>,
+) {
+    let Ok((fx, transform)) = query.get(add.entity) else {
+        return;
+    };
+    if let Some(flash) = fx.flash {
+        commands.spawn(burst(
+            &anim_assets,
+            flash,
+            transform.translation.xy(),
+            fx.color,
+        ));
+    }
+}
+
+fn spawn_impact(
+    impact: On<Impact>,
+    mut commands: Commands,
+    anim_assets: If<Res<AnimationAssets>>,
+    query: Query<(&ProjectileFx, &Transform)>,
+) {
+    let Ok((fx, transform)) = query.get(impact.entity) else {
+        return;
+    };
+    if let Some(hit) = fx.impact {
+        commands.spawn(burst(
+            &anim_assets,
+            hit,
+            transform.translation.xy(),
+            fx.color,
+        ));
+    }
+}
+
+fn despawn_finished_bursts(
+    mut commands: Commands,
+    mut events: MessageReader<AnimationEvents>,
+    query: Query<(), With<BurstAnimation>>,
+) {
+    for event in events.read() {
+        if let AnimationEvents::Finished(entity) = event
+            && query.contains(*entity)
+        {
+            commands.entity(*entity).try_despawn();
+        }
+    }
+}
+
+fn update_trails(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(&mut ProjectileFx, &Sprite, &Transform)>,
+) {
+    for (mut fx, sprite, transform) in &mut query {
+        let Some(trail) = fx.trail.as_mut() else {
+            continue;
+        };
+        if !trail.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let fade_secs = trail.fade_secs;
+        let mut afterimage = sprite.clone();
+        afterimage.color = fx.color;
+        let mut afterimage_transform = *transform;
+        afterimage_transform.translation.z -= 1.0;
+        commands.spawn((
+            Name::new("Projectile Afterimage"),
+            afterimage,
+            afterimage_transform,
+            Fade::new(fade_secs, 0.5, -0.5),
+        ));
+    }
+}
+
+fn update_fades(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Fade, &mut Sprite, &mut Transform)>,
+) {
+    for (entity, mut fade, mut sprite, mut transform) in &mut query {
+        fade.timer.tick(time.delta());
+        if fade.timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let t = fade.timer.fraction();
+        sprite.color.set_alpha(fade.from_alpha * (1.0 - t));
+        transform.scale = Vec3::splat(1.0 + fade.grow * t);
+    }
+}
@@ -1,17 +1,33 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use bevy_aseprite_ultra::prelude::{Animation, AnimationDirection, AnimationRepeat, AseAnimation};
+use bevy_aseprite_ultra::prelude::{
+    Animation, AnimationDirection, AnimationRepeat, AseAnimation, Aseprite,
+};
 
 use crate::{
     PausableSystems,
     audio::sound_effect,
-    game::{animation::*, movement::*, player::*},
-    screens::gameplay::GameplayLifetime,
+    game::{
+        animation::*,
+        level::projectile_fx::{ProjectileFx, despawn_with_impact},
+        movement::*,
+        player::*,
+    },
+    screens::{Screen, gameplay::GameplayLifetime},
 };
 
 pub const PROJECTILE_Z_TRANSLATION: f32 = PLAYER_Z_TRANSLATION;
 pub const SOURCE_Z_TRANSLATION: f32 = PLAYER_Z_TRANSLATION;
 
+// Before [`Difficulty::bullet_speed`](crate::game::difficulty::Difficulty::bullet_speed)
+pub const ENEMY_BULLET_SPEED: f32 = 40.0;
+pub const BOSS_BULLET_SPEED: f32 = 120.0;
+
+// Friendly projectile colors for flashes, bursts and trails
+const CHAKRAM_FX_COLOR: Color = Color::srgb(1.0, 0.753, 0.0);
+const BOUNCE_DOWN_FX_COLOR: Color = Color::srgb(0.55, 0.85, 1.0);
+const LIFESPAN_FX_COLOR: Color = Color::srgb(0.8, 1.0, 0.6);
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, update_sources.in_set(PausableSystems));
     app.add_systems(
@@ -20,18 +36,18 @@ pub(super) fn plugin(app: &mut App) {
             .in_set(PausableSystems)
             .chain(),
     );
-    // Run after collision so we see Friendly removals when projectiles hit enemies/walls.
-    app.add_systems(
-        FixedUpdate,
-        restore_ammo.in_set(PausableSystems).after(on_collision),
-    );
+    app.init_resource::<RecallSettings>();
+    app.add_observer(restore_ammo)
+        .add_observer(apply_charged_shot)
+        .add_observer(apply_return_damage);
 }
 
 #[derive(Asset, Clone, Reflect)]
 pub struct ProjectileAssets {
     #[dependency]
     pub ricochet: Vec<Handle<AudioSource>>,
-    pub bullet: Handle<Image>,
+    /// Spawn and impact animations of every projectile type
+    pub fx: Handle<Aseprite>,
 }
 
 /// Tile property
@@ -72,6 +88,44 @@ pub struct Friendly;
 #[derive(Component, Default, Debug)]
 pub struct Hostile;
 
+/// Ammo given back to the [`Player`] once the friendly projectile is gone.
+/// Projectiles without it give back 1.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct AmmoCost(pub usize);
+
+/// Lives taken from an enemy on hit. Projectiles without it take 1.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Damage(pub usize);
+
+/// Not consumed by the enemies it hits. It flies through them without a
+/// contact and hurts each one once.
+#[derive(Component, Debug, Default)]
+#[require(ActiveCollisionHooks::FILTER_PAIRS)]
+pub struct Piercing {
+    /// Enemies already hit. They are not hit twice.
+    pub hits: Vec<Entity>,
+}
+
+/// Power of a held throw, from 0 (tap) to 1 (full). Applied once when inserted:
+/// scales speed, bounce count and damage, and a full charge pierces.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ChargedShot(pub f32);
+
+impl ChargedShot {
+    pub const MAX_SPEED_SCALE: f32 = 2.0;
+    pub const MAX_EXTRA_BOUNCES: usize = 3;
+    pub const MAX_EXTRA_DAMAGE: usize = 2;
+
+    pub fn is_full(&self) -> bool {
+        self.0 >= 1.0
+    }
+}
+
+/// Bounces on top of the projectile's own [`Due::BounceDown`], applied with
+/// the [`ChargedShot`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ExtraBounces(pub usize);
+
 /// Define how projectile is resolved beside hit
 /// Not gonna use enumset
 #[derive(Debug, PartialEq, Eq)]
@@ -144,14 +198,17 @@ pub fn player_chakra<HostilityComponent: Component + Default>(
             aseprite: anim_assets.player.chakram.clone(),
         },
         Sprite::default(),
+        ProjectileFx::new(CHAKRAM_FX_COLOR)
+            .with_flash("Chakram Spawn", 10.0)
+            .with_impact("Chakram Impact", 8.0)
+            .with_trail(0.03, 0.15),
         ScreenWrap,
         LockedAxes::new().lock_rotation(),
         Transform::from_xyz(new_xy.x, new_xy.y, PROJECTILE_Z_TRANSLATION),
         RigidBody::Dynamic,
         GravityScale(0.0),
         Collider::circle(player_projectile_collider_radius),
-        Restitution::new(2.0),
-        Friction::new(0.0),
+        (Restitution::new(2.0), Friction::new(0.0)),
     )
 }
 
@@ -159,12 +216,12 @@ pub fn enemy_basic_bullet<HostilityComponent: Component + Default>(
     xy: Vec2,
     direction: Dir2,
     thrower_radius: f32,
+    speed: f32,
     anim_assets: &AnimationAssets,
     color: Color,
 ) -> impl Bundle {
     let lifespan_projectile_collider_radius: f32 = 2.;
     let projectile_life: f32 = 2.; // seconds
-    let speed: f32 = 40.0;
 
     let new_xy = (lifespan_projectile_collider_radius + thrower_radius + 1.0e-3) * direction + xy;
     (
@@ -186,6 +243,7 @@ pub fn enemy_basic_bullet<HostilityComponent: Component + Default>(
             color,
             ..default()
         },
+        ProjectileFx::new(color).with_impact("Bullet Impact", 8.0),
         // ScreenWrap,
         LockedAxes::new().lock_rotation(),
         Transform::from_xyz(new_xy.x, new_xy.y, PROJECTILE_Z_TRANSLATION),
@@ -201,12 +259,12 @@ pub fn boss_basic_bullet<HostilityComponent: Component + Default>(
     xy: Vec2,
     direction: Dir2,
     thrower_radius: f32,
+    speed: f32,
     anim_assets: &AnimationAssets,
     color: Color,
 ) -> impl Bundle {
     let lifespan_projectile_collider_radius: f32 = 1.5;
     let projectile_life: f32 = 2.0; // seconds
-    let speed: f32 = 120.0;
 
     let new_xy = (lifespan_projectile_collider_radius + thrower_radius + 1.0e-3) * direction + xy;
     (
@@ -228,6 +286,10 @@ pub fn boss_basic_bullet<HostilityComponent: Component + Default>(
             color,
             ..default()
         },
+        ProjectileFx::new(color)
+            .with_flash("Bullet Spawn", 12.0)
+            .with_impact("Bullet Impact", 10.0)
+            .with_trail(0.05, 0.2),
         ScreenWrap,
         LockedAxes::new().lock_rotation(),
         Transform::from_xyz(new_xy.x, new_xy.y, PROJECTILE_Z_TRANSLATION),
@@ -239,7 +301,6 @@ pub fn boss_basic_bullet<HostilityComponent: Component + Default>(
     )
 }
 
-#[allow(dead_code)]
 pub fn basic_projectile<HostilityComponent: Component + Default>(
     xy: Vec2,
     direction: Dir2,
@@ -270,6 +331,9 @@ pub fn basic_projectile<HostilityComponent: Component + Default>(
             aseprite: anim_assets.player.chakram.clone(),
         },
         Sprite::default(),
+        ProjectileFx::new(CHAKRAM_FX_COLOR)
+            .with_flash("Chakram Spawn", 10.0)
+            .with_impact("Chakram Impact", 8.0),
         ScreenWrap,
         LockedAxes::new().lock_rotation(),
         Transform::from_xyz(new_xy.x, new_xy.y, PROJECTILE_Z_TRANSLATION),
@@ -281,8 +345,6 @@ pub fn basic_projectile<HostilityComponent: Component + Default>(
 }
 
 /// Example of projectile that's gone when it bounces more than certain time
-/// TODO: visual using AnimationAssets
-#[allow(dead_code)]
 pub fn bounce_down_projectile<HostilityComponent: Component + Default>(
     xy: Vec2,
     direction: Dir2,
@@ -302,7 +364,23 @@ pub fn bounce_down_projectile<HostilityComponent: Component + Default>(
         HostilityComponent::default(),
         LinearVelocity(speed * direction.as_vec2()),
         LinearDamping(0.0),
-        //Sprite::default(),
+        AseAnimation {
+            animation: Animation::tag("Spin")
+                .with_repeat(AnimationRepeat::Loop)
+                .with_direction(AnimationDirection::Forward)
+                .with_speed(2.0),
+            aseprite: anim_assets.player.chakram.clone(),
+        },
+        (
+            Sprite {
+                color: BOUNCE_DOWN_FX_COLOR,
+                ..default()
+            },
+            ProjectileFx::new(BOUNCE_DOWN_FX_COLOR)
+                .with_flash("Bounce Spawn", 10.0)
+                .with_impact("Bounce Impact", 12.0)
+                .with_trail(0.02, 0.2),
+        ),
         ScreenWrap,
         LockedAxes::new().lock_rotation(),
         Transform::from_xyz(new_xy.x, new_xy.y, PROJECTILE_Z_TRANSLATION),
@@ -314,8 +392,6 @@ pub fn bounce_down_projectile<HostilityComponent: Component + Default>(
 }
 
 /// Example of projectile that has lifespan
-/// TODO: visual using AnimationAssets
-#[allow(dead_code)]
 pub fn lifespan_projectile<HostilityComponent: Component + Default>(
     xy: Vec2,
     direction: Dir2,
@@ -342,8 +418,13 @@ pub fn lifespan_projectile<HostilityComponent: Component + Default>(
         Sprite {
             image: anim_assets.enemies.bullet.clone(),
             custom_size: Some(Vec2::new(8.0, 8.0)),
+            color: LIFESPAN_FX_COLOR,
             ..default()
         },
+        ProjectileFx::new(LIFESPAN_FX_COLOR)
+            .with_flash("Lifespan Spawn", 8.0)
+            .with_impact("Lifespan Impact", 6.0)
+            .with_trail(0.04, 0.12),
         ScreenWrap,
         LockedAxes::new().lock_rotation(),
         Transform::from_xyz(new_xy.x, new_xy.y, PROJECTILE_Z_TRANSLATION),
@@ -354,6 +435,35 @@ pub fn lifespan_projectile<HostilityComponent: Component + Default>(
     )
 }
 
+fn apply_charged_shot(
+    add: On<Add, ChargedShot>,
+    mut commands: Commands,
+    mut query: Query<(
+        &ChargedShot,
+        &mut Projectile,
+        &mut LinearVelocity,
+        Option<&ExtraBounces>,
+    )>,
+) {
+    let Ok((shot, mut projectile, mut velocity, bonus)) = query.get_mut(add.entity) else {
+        return;
+    };
+    let power = shot.0.clamp(0.0, 1.0);
+    velocity.0 *= 1.0 + (ChargedShot::MAX_SPEED_SCALE - 1.0) * power;
+    let extra_bounces =
+        (ChargedShot::MAX_EXTRA_BOUNCES as f32 * power).round() as usize + bonus.map_or(0, |b| b.0);
+    for due in projectile.dues.iter_mut() {
+        if let Due::BounceDown(count) = due {
+            *count += extra_bounces;
+        }
+    }
+    let damage = 1 + (ChargedShot::MAX_EXTRA_DAMAGE as f32 * power).floor() as usize;
+    commands.entity(add.entity).insert(Damage(damage));
+    if shot.is_full() {
+        commands.entity(add.entity).insert(Piercing::default());
+    }
+}
+
 // Project due process except what's collision based
 fn update_projectiles(
     mut commands: Commands,
@@ -381,21 +491,31 @@ fn update_projectiles(
             }
         }
     }
-    despawned.iter().for_each(|&e| commands.entity(e).despawn());
+    despawned
+        .iter()
+        .for_each(|&e| despawn_with_impact(&mut commands, e));
 }
 
+/// Runs right before [`Friendly`] goes away (a hit, a catch or a despawn) so the
+/// [`AmmoCost`] of the projectile can still be read. Projectiles cleared away
+/// with the level, once the screen has already changed, give nothing back.
 fn restore_ammo(
+    remove: On<Remove, Friendly>,
     mut commands: Commands,
-    mut player: Single<&mut Player>,
-    mut removed: RemovedComponents<Friendly>,
-    anim_assets: Res<AnimationAssets>,
+    screen: Res<State<Screen>>,
+    mut player: Query<&mut Player>,
+    cost_query: Query<&AmmoCost>,
+    anim_assets: If<Res<AnimationAssets>>,
 ) {
-    if !removed.is_empty() {
-        let mut p = player.into_inner();
-        commands.spawn(sound_effect(anim_assets.player.pickup.clone()));
-        p.increment_ammo(removed.len());
+    if *screen.get() != Screen::Gameplay {
+        return;
     }
-    removed.clear();
+    let Ok(mut p) = player.single_mut() else {
+        return;
+    };
+    let n = cost_query.get(remove.entity).map_or(1, |cost| cost.0);
+    commands.spawn(sound_effect(anim_assets.player.pickup.clone()));
+    p.increment_ammo(n);
 }
 
 /// Player Projectile Cooldown - limit the projectiles player can have thrown at a time
@@ -451,24 +571,111 @@ impl Default for Source {
 }
 
 #[derive(Component)]
-#[require(ActiveCollisionHooks::FILTER_PAIRS)]
+#[require(ActiveCollisionHooks::FILTER_PAIRS, RecallState)]
 pub struct Recalled;
 
+/// Book keeping of a recalled projectile on its way back
+#[derive(Component, Debug, Default)]
+pub struct RecallState {
+    /// Seconds since recalled
+    pub secs: f32,
+    /// Enemies already hit on the way back. They are not hit twice.
+    pub hits: Vec<Entity>,
+}
+
+/// Tuning of how recalled projectiles come back. Meant to be tweaked to compare feel.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct RecallSettings {
+    pub path: RecallPath,
+    pub speed: f32,
+    /// Damage on the way back relative to the throw damage, rounded down but
+    /// never below 1. At 0 returns ghost through enemies.
+    pub return_damage_scale: f32,
+    /// Bounce off walls on the way back instead of ghosting through them
+    pub bounce_walls: bool,
+    /// Walls are ghosted anyway after this so a chakram can't get stuck behind one
+    pub ghost_after_secs: f32,
+}
+
+impl Default for RecallSettings {
+    fn default() -> Self {
+        Self {
+            path: RecallPath::Arc {
+                bend: 50.0_f32.to_radians(),
+                falloff_distance: 150.0,
+            },
+            speed: 500.0,
+            return_damage_scale: 0.5,
+            bounce_walls: true,
+            ghost_after_secs: 1.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub enum RecallPath {
+    /// Straight at the player
+    Straight,
+    /// Boomerang arc: heads off by `bend` radians from the player and
+    /// straightens up within `falloff_distance`
+    Arc { bend: f32, falloff_distance: f32 },
+    /// Turns toward the player by at most `turn_rate` radians per second
+    Steer { turn_rate: f32 },
+}
+
+/// Scale down the damage once recalled. Any scale above 0 keeps at least 1
+/// damage; at 0 the projectile passes through enemies (see `PassthroughHook`).
+fn apply_return_damage(
+    add: On<Add, Recalled>,
+    mut commands: Commands,
+    settings: Res<RecallSettings>,
+    query: Query<Option<&Damage>>,
+) {
+    let Ok(damage) = query.get(add.entity) else {
+        return;
+    };
+    let thrown = damage.map_or(1, |d| d.0);
+    let mut returned = (thrown as f32 * settings.return_damage_scale).floor() as usize;
+    if settings.return_damage_scale > 0.0 {
+        returned = returned.max(thrown.min(1));
+    }
+    commands.entity(add.entity).insert(Damage(returned));
+}
+
 fn apply_recall_homing(
-    mut query: Query<(&mut LinearVelocity, &Transform), With<Recalled>>,
+    time: Res<Time>,
+    settings: Res<RecallSettings>,
+    mut query: Query<(&mut LinearVelocity, &mut RecallState, &Transform), With<Recalled>>,
     player_query: Single<&Transform, With<Player>>,
 ) {
     let player_transform = player_query.into_inner();
     let player_pos = player_transform.translation.xy();
 
-    for (mut velocity, proj_transform) in &mut query {
+    for (mut velocity, mut state, proj_transform) in &mut query {
+        state.secs += time.delta_secs();
         let proj_pos = proj_transform.translation.xy();
 
         // Calculate direction to player
-        let direction = (player_pos - proj_pos).normalize_or_zero();
-
-        // You can make the recall speed faster than the throw speed (e.g., 500.0)
-        let recall_speed = 500.0;
-        velocity.0 = direction * recall_speed;
+        let to_player = player_pos - proj_pos;
+        let direction = to_player.normalize_or_zero();
+
+        let direction = match settings.path {
+            RecallPath::Straight => direction,
+            RecallPath::Arc {
+                bend,
+                falloff_distance,
+            } => {
+                let t = (to_player.length() / falloff_distance).min(1.0);
+                Vec2::from_angle(bend * t).rotate(direction)
+            }
+            RecallPath::Steer { turn_rate } => {
+                let current = velocity.0.normalize_or(direction);
+                let max_turn = turn_rate * time.delta_secs();
+                let turn = current.angle_to(direction).clamp(-max_turn, max_turn);
+                Vec2::from_angle(turn).rotate(current)
+            }
+        };
+        velocity.0 = direction * settings.speed;
     }
 }
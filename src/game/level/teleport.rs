@@ -0,0 +1,214 @@
+//! Bosses blinking between fixed spots.
+//! A teleport starts on a timer, when life drops to a threshold, or before an
+//! attack that asks for one. It picks a spot that is clear of colliders and
+//! not on top of the player. The boss fades out, moves, and fades back in.
+//! It holds its fire and stands still the whole time.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_aseprite_ultra::prelude::*;
+use rand::seq::IndexedRandom;
+use serde::Deserialize;
+
+use crate::{
+    PausableSystems,
+    game::{
+        level::{
+            boss_phases::PhaseTransition, bosses::BossIntroPlaying, enemies::Enemy,
+            projectiles::Projectile,
+        },
+        player::Player,
+        rng::GameRng,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (trigger_teleports, update_teleports)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Component, Debug)]
+pub struct TeleportAbility {
+    pub positions: Vec<Vec2>,
+    pub timer: Timer,
+    pub current_index: usize,
+    pub choice: TeleportChoice,
+    /// Also teleports once life drops to each of these
+    pub at_life: Vec<usize>,
+    /// Radius that has to be free of colliders around a spot
+    pub clearance: f32,
+    /// Spots closer to the player than this are skipped
+    pub min_player_distance: f32,
+    /// Aseprite tags to play while fading out and in
+    pub disappear: Option<String>,
+    pub reappear: Option<String>,
+    /// Seconds to fade out, and again to fade in
+    pub fade_secs: f32,
+    /// [`Enemy::attacks_finished`] when an attack last asked for a teleport
+    pub attack_run: Option<usize>,
+}
+
+/// Which clear spot to go to
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TeleportChoice {
+    /// The next one in the list
+    #[default]
+    InOrder,
+    Random,
+    /// The one farthest from the player
+    Farthest,
+}
+
+/// Fading out toward `to`, then fading back in once `arrived`
+#[derive(Component, Debug)]
+pub struct Teleporting {
+    pub to: Vec2,
+    pub timer: Timer,
+    pub arrived: bool,
+}
+
+fn trigger_teleports(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    spatial_query: SpatialQuery,
+    player: Option<Single<&Transform, With<Player>>>,
+    projectiles: Query<Entity, With<Projectile>>,
+    mut query: Query<
+        (
+            Entity,
+            &Transform,
+            &Enemy,
+            &mut TeleportAbility,
+            &mut LinearVelocity,
+            Option<&mut AseAnimation>,
+        ),
+        (
+            Without<Teleporting>,
+            Without<BossIntroPlaying>,
+            Without<PhaseTransition>,
+        ),
+    >,
+) {
+    let player_pos = player.map(|t| t.translation.xy());
+    for (entity, transform, enemy, mut ability, mut velocity, animation) in &mut query {
+        if enemy.life == 0 {
+            continue;
+        }
+        let on_timer = ability.timer.tick(time.delta()).just_finished();
+        let mut on_life = false;
+        while ability.at_life.first().is_some_and(|at| enemy.life <= *at) {
+            ability.at_life.remove(0);
+            on_life = true;
+        }
+        let on_attack = !enemy.attacks.is_empty()
+            && enemy.attacks[enemy.attack_idx % enemy.attacks.len()].teleport
+            && ability.attack_run != Some(enemy.attacks_finished);
+        if on_attack {
+            ability.attack_run = Some(enemy.attacks_finished);
+        }
+        if !(on_timer || on_life || on_attack) {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+        // Don't count the boss itself or bullets flying through the spot
+        let filter = SpatialQueryFilter::default()
+            .with_excluded_entities(projectiles.iter().chain(std::iter::once(entity)));
+        let shape = Collider::circle(ability.clearance);
+        let is_clear = |spot: Vec2| {
+            spatial_query
+                .shape_intersections(&shape, spot, 0.0, &filter)
+                .is_empty()
+        };
+        let Some(to) = pick_spot(&mut ability, position, player_pos, is_clear, &mut *rng) else {
+            debug!("{entity}: no clear spot to teleport to");
+            continue;
+        };
+
+        *velocity = LinearVelocity::ZERO;
+        if let (Some(tag), Some(mut animation)) = (&ability.disappear, animation) {
+            animation.animation.play(tag, AnimationRepeat::Count(1));
+            animation.animation.then("Idle", AnimationRepeat::Loop);
+        }
+        commands.entity(entity).insert(Teleporting {
+            to,
+            timer: Timer::from_seconds(ability.fade_secs, TimerMode::Once),
+            arrived: false,
+        });
+    }
+}
+
+fn pick_spot(
+    ability: &mut TeleportAbility,
+    position: Vec2,
+    player: Option<Vec2>,
+    is_clear: impl Fn(Vec2) -> bool,
+    rng: &mut GameRng,
+) -> Option<Vec2> {
+    let n = ability.positions.len();
+    // In list order, starting from the next one
+    let candidates: Vec<(usize, Vec2)> = (0..n)
+        .map(|i| (ability.current_index + i) % n)
+        .map(|i| (i, ability.positions[i]))
+        .filter(|(_, spot)| spot.distance(position) > ability.clearance)
+        .filter(|(_, spot)| player.is_none_or(|p| spot.distance(p) >= ability.min_player_distance))
+        .filter(|(_, spot)| is_clear(*spot))
+        .collect();
+    let (index, spot) = match ability.choice {
+        TeleportChoice::InOrder => candidates.first().copied(),
+        TeleportChoice::Random => candidates.choose(rng.gameplay()).copied(),
+        TeleportChoice::Farthest => {
+            let from = player.unwrap_or(position);
+            candidates
+                .iter()
+                .copied()
+                .max_by(|(_, a), (_, b)| a.distance(from).total_cmp(&b.distance(from)))
+        }
+    }?;
+    ability.current_index = (index + 1) % n;
+    Some(spot)
+}
+
+fn update_teleports(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Teleporting,
+        &mut Sprite,
+        Option<&TeleportAbility>,
+        Option<&mut AseAnimation>,
+    )>,
+) {
+    for (entity, mut transform, mut teleporting, mut sprite, ability, animation) in &mut query {
+        teleporting.timer.tick(time.delta());
+        let t = teleporting.timer.fraction();
+        sprite
+            .color
+            .set_alpha(if teleporting.arrived { t } else { 1.0 - t });
+        if !teleporting.timer.is_finished() {
+            continue;
+        }
+        if teleporting.arrived {
+            sprite.color.set_alpha(1.0);
+            commands.entity(entity).remove::<Teleporting>();
+            continue;
+        }
+        transform.translation.x = teleporting.to.x;
+        transform.translation.y = teleporting.to.y;
+        teleporting.arrived = true;
+        teleporting.timer.reset();
+        if let (Some(tag), Some(mut animation)) =
+            (ability.and_then(|a| a.reappear.as_ref()), animation)
+        {
+            animation.animation.play(tag, AnimationRepeat::Count(1));
+            animation.animation.then("Idle", AnimationRepeat::Loop);
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Minions joining the boss fight, described in `assets/waves/*.waves.ron`.
+//! A [`WaveDefinition`] lists the minions and the waves. They come in at the
+//! `SpawnPoint` objects of the Tiled map, or at the spawn points of the
+//! definition for a map without any.
+//! Waves start in order, each once its [`WaveTrigger`] holds. Their minions
+//! come out one by one while fewer than `max_alive` are up.
+//! The same file places the breakable props of the level.
+
+use std::collections::VecDeque;
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use bevy_aseprite_ultra::prelude::*;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+    PausableSystems,
+    asset_tracking::LoadResource,
+    game::{
+        difficulty::Difficulty,
+        level::{
+            Level,
+            boss_definition::{AttackDef, MoveDef},
+            bosses::{Boss, BossFlow, BossIntroPlaying},
+            enemies::Enemy,
+            pickups::{BreakableDef, DropTable},
+        },
+        movement::ScreenWrap,
+        player::PLAYER_Z_TRANSLATION,
+    },
+    screens::Screen,
+    ui::dialogue::DialogueQueue,
+    utils::tiled::{TiledMap, TiledMapHandle},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<WaveDefinition>()
+        .register_asset_loader(WaveDefinitionLoader)
+        .load_resource::<LevelWaves>();
+    app.add_systems(OnEnter(Screen::Gameplay), start_waves)
+        .add_systems(OnExit(Screen::Gameplay), stop_waves);
+    app.add_systems(
+        Update,
+        (trigger_waves, spawn_minions)
+            .chain()
+            .in_set(PausableSystems)
+            .run_if(
+                resource_exists::<WaveProgress>
+                    .and(not(resource_exists::<DialogueQueue>))
+                    .and(not(any_with_component::<BossIntroPlaying>)),
+            ),
+    );
+}
+
+/// Seconds between two minions of the same wave
+const SPAWN_INTERVAL_SECS: f32 = 0.4;
+
+/// The waves of each level. The tutorial has none.
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LevelWaves {
+    #[dependency]
+    pub phase1: Handle<WaveDefinition>,
+    #[dependency]
+    pub phase2: Handle<WaveDefinition>,
+    #[dependency]
+    pub phase3: Handle<WaveDefinition>,
+    /// For [`BossFlow::SingleFight`]
+    #[dependency]
+    pub single_fight: Handle<WaveDefinition>,
+}
+
+impl FromWorld for LevelWaves {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            phase1: assets.load("waves/phase1.waves.ron"),
+            phase2: assets.load("waves/phase2.waves.ron"),
+            phase3: assets.load("waves/phase3.waves.ron"),
+            single_fight: assets.load("waves/narak.waves.ron"),
+        }
+    }
+}
+
+impl LevelWaves {
+    pub fn for_level(&self, level: Level, flow: BossFlow) -> Option<&Handle<WaveDefinition>> {
+        use Level::*;
+        match level {
+            Tutorial => None,
+            Phase1 if flow == BossFlow::SingleFight => Some(&self.single_fight),
+            Phase1 => Some(&self.phase1),
+            Phase2 => Some(&self.phase2),
+            Phase3 => Some(&self.phase3),
+        }
+    }
+}
+
+#[derive(Asset, TypePath, Debug)]
+pub struct WaveDefinition {
+    /// Map coordinates, used in turn when the map has no spawn points
+    pub spawn_points: Vec<Vec2>,
+    pub max_alive: usize,
+    pub minions: Vec<MinionDef>,
+    pub waves: Vec<Wave>,
+    pub breakables: Vec<BreakableDef>,
+}
+
+#[derive(Debug)]
+pub struct MinionDef {
+    pub name: String,
+    pub life: usize,
+    pub collider_radius: f32,
+    pub shooting_range: f32,
+    pub bullet_colors: Vec<Color>,
+    /// Relative to the spawn point. Wanders randomly if empty.
+    pub moves: Vec<MoveDef>,
+    pub attacks: Vec<AttackDef>,
+    pub drops: DropTable,
+    /// Loaded with the definition
+    pub aseprite: Handle<Aseprite>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Wave {
+    pub trigger: WaveTrigger,
+    /// Name of a minion in the definition
+    pub minion: String,
+    pub count: usize,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub enum WaveTrigger {
+    /// Seconds into the fight
+    After(f32),
+    /// Normal boss life, scaled by the [`Difficulty`]
+    BossLifeAtMost(usize),
+    /// Every minion of the earlier waves is down
+    Cleared,
+}
+
+/// The waves of the current level as they play out
+#[derive(Resource, Debug)]
+pub struct WaveProgress {
+    pub definition: Handle<WaveDefinition>,
+    /// Seconds into the fight
+    pub elapsed: f32,
+    /// Waves started so far
+    pub started: usize,
+    pub total: usize,
+    /// Minions still to come out, by index in [`WaveDefinition::minions`]
+    pub pending: VecDeque<usize>,
+    pub spawn_timer: Timer,
+    next_point: usize,
+}
+
+impl WaveProgress {
+    fn new(definition: Handle<WaveDefinition>, total: usize) -> Self {
+        Self {
+            definition,
+            elapsed: 0.0,
+            started: 0,
+            total,
+            pending: VecDeque::new(),
+            spawn_timer: Timer::from_seconds(SPAWN_INTERVAL_SECS, TimerMode::Repeating),
+            next_point: 0,
+        }
+    }
+}
+
+/// A regular enemy brought in by a wave
+#[derive(Component, Debug)]
+pub struct Minion;
+
+fn start_waves(
+    mut commands: Commands,
+    current_level: Res<State<Level>>,
+    flow: Res<BossFlow>,
+    level_waves: Res<LevelWaves>,
+    definitions: Res<Assets<WaveDefinition>>,
+) {
+    let Some(handle) = level_waves.for_level(*current_level.get(), *flow) else {
+        return;
+    };
+    match definitions.get(handle) {
+        Some(definition) if !definition.waves.is_empty() => {
+            commands.insert_resource(WaveProgress::new(handle.clone(), definition.waves.len()));
+        }
+        Some(_) => (),
+        None => error!("Waves for {:?} are not loaded", current_level.get()),
+    }
+}
+
+fn stop_waves(mut commands: Commands) {
+    commands.remove_resource::<WaveProgress>();
+}
+
+fn trigger_waves(
+    time: Res<Time>,
+    mut progress: ResMut<WaveProgress>,
+    definitions: Res<Assets<WaveDefinition>>,
+    difficulty: Res<Difficulty>,
+    boss: Option<Single<&Enemy, With<Boss>>>,
+    minions: Query<&Enemy, With<Minion>>,
+) {
+    progress.elapsed += time.delta_secs();
+    let Some(definition) = definitions.get(&progress.definition) else {
+        return;
+    };
+    let boss_life = boss.map(|enemy| enemy.life);
+    // The fight is over
+    if boss_life == Some(0) {
+        return;
+    }
+    let Some(wave) = definition.waves.get(progress.started) else {
+        return;
+    };
+    let holds = match wave.trigger {
+        WaveTrigger::After(secs) => progress.elapsed >= secs,
+        WaveTrigger::BossLifeAtMost(life) => {
+            boss_life.is_some_and(|l| l <= difficulty.boss_life(life))
+        }
+        WaveTrigger::Cleared => {
+            progress.pending.is_empty() && minions.iter().all(|minion| minion.life == 0)
+        }
+    };
+    if !holds {
+        return;
+    }
+    progress.started += 1;
+    match definition
+        .minions
+        .iter()
+        .position(|m| m.name == wave.minion)
+    {
+        Some(minion) => {
+            info!("Wave {} of {}", progress.started, progress.total);
+            progress
+                .pending
+                .extend(std::iter::repeat_n(minion, wave.count));
+        }
+        None => warn!(
+            "Wave {}: no minion named {:?}",
+            progress.started, wave.minion
+        ),
+    }
+}
+
+fn spawn_minions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut progress: ResMut<WaveProgress>,
+    definitions: Res<Assets<WaveDefinition>>,
+    map: Option<Single<&TiledMapHandle>>,
+    maps: Res<Assets<TiledMap>>,
+    minions: Query<&Enemy, With<Minion>>,
+) {
+    if progress.pending.is_empty() {
+        return;
+    }
+    if !progress.spawn_timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Some(definition) = definitions.get(&progress.definition) else {
+        return;
+    };
+    let spawn_points = map
+        .and_then(|handle| maps.get(&handle.0))
+        .map(|map| &map.spawn_points)
+        .filter(|points| !points.is_empty())
+        .unwrap_or(&definition.spawn_points);
+    let alive = minions.iter().filter(|minion| minion.life > 0).count();
+    if alive >= definition.max_alive || spawn_points.is_empty() {
+        return;
+    }
+    let Some(index) = progress.pending.pop_front() else {
+        return;
+    };
+    let xy = spawn_points[progress.next_point % spawn_points.len()];
+    progress.next_point += 1;
+    commands.spawn(minion(&definition.minions[index], xy));
+}
+
+fn minion(def: &MinionDef, xy: Vec2) -> impl Bundle {
+    let mut enemy = Enemy::new(def.life)
+        .with_shooting_range(def.shooting_range)
+        .with_bullet_colors(def.bullet_colors.clone())
+        .with_move_pattern(def.moves.iter().map(|m| m.to_move(xy)).collect());
+    for attack in &def.attacks {
+        enemy = enemy.with_attack(attack.to_attack());
+    }
+    (
+        Name::new(def.name.clone()),
+        Minion,
+        enemy,
+        def.drops.clone(),
+        AseAnimation {
+            animation: Animation::tag("Idle")
+                .with_repeat(AnimationRepeat::Loop)
+                .with_direction(AnimationDirection::Forward)
+                .with_speed(1.0),
+            aseprite: def.aseprite.clone(),
+        },
+        Sprite::default(),
+        ScreenWrap,
+        Transform::from_xyz(xy.x, xy.y, PLAYER_Z_TRANSLATION),
+        RigidBody::Dynamic,
+        LockedAxes::ROTATION_LOCKED,
+        GravityScale(0.0),
+        Collider::circle(def.collider_radius),
+    )
+}
+
+#[derive(TypePath)]
+pub struct WaveDefinitionLoader;
+
+#[derive(Debug, Error)]
+pub enum WaveDefinitionLoaderError {
+    /// An [IO](std::io) Error
+    #[error("Could not load wave definition: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse wave definition: {0}")]
+    Ron(#[from] ron::de::SpannedError),
+}
+
+/// The file as written, with asset paths instead of handles
+#[derive(Deserialize)]
+struct WaveDefinitionFile {
+    #[serde(default)]
+    spawn_points: Vec<[f32; 2]>,
+    max_alive: usize,
+    minions: Vec<MinionFile>,
+    waves: Vec<Wave>,
+    #[serde(default)]
+    breakables: Vec<BreakableDef>,
+}
+
+#[derive(Deserialize)]
+struct MinionFile {
+    name: String,
+    life: usize,
+    collider_radius: f32,
+    shooting_range: f32,
+    /// sRGB
+    bullet_colors: Vec<[f32; 3]>,
+    #[serde(default)]
+    moves: Vec<MoveDef>,
+    #[serde(default)]
+    attacks: Vec<AttackDef>,
+    #[serde(default)]
+    drops: DropTable,
+    aseprite: String,
+}
+
+impl AssetLoader for WaveDefinitionLoader {
+    type Asset = WaveDefinition;
+    type Settings = ();
+    type Error = WaveDefinitionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: WaveDefinitionFile = ron::de::from_bytes(&bytes)?;
+
+        Ok(WaveDefinition {
+            spawn_points: file.spawn_points.into_iter().map(Vec2::from).collect(),
+            max_alive: file.max_alive,
+            minions: file
+                .minions
+                .into_iter()
+                .map(|minion| MinionDef {
+                    name: minion.name,
+                    life: minion.life,
+                    collider_radius: minion.collider_radius,
+                    shooting_range: minion.shooting_range,
+                    bullet_colors: minion
+                        .bullet_colors
+                        .iter()
+                        .map(|[r, g, b]| Color::srgb(*r, *g, *b))
+                        .collect(),
+                    moves: minion.moves,
+                    attacks: minion.attacks,
+                    drops: minion.drops,
+                    aseprite: load_context.load(minion.aseprite),
+                })
+                .collect(),
+            waves: file.waves,
+            breakables: file.breakables,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["waves.ron"]
+    }
+}
@@ -2,21 +2,39 @@
 //! purposes and should be replaced with your own game logic.
 //! Feel free to change the logic found here if you feel like tinkering around
 //! to get a feeling for the template.
+pub mod aim;
 mod animation;
+pub mod dash;
+pub mod difficulty;
 pub mod level;
 pub mod movement;
 pub mod player;
+pub mod rng;
+pub mod run;
+pub mod save;
+pub mod upgrades;
+pub mod weapon;
 
 use bevy::prelude::*;
 
+use crate::PausableSystems;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
+        aim::plugin,
         animation::plugin,
+        dash::plugin,
+        difficulty::plugin,
         level::plugin,
         movement::plugin,
         player::plugin,
+        rng::plugin,
+        run::plugin,
+        save::plugin,
+        weapon::plugin,
     ));
-    app.add_systems(Update, update_red);
+    // Paused with the game so a blink lasts exactly as long as the i-frames
+    app.add_systems(Update, update_red.in_set(PausableSystems));
 }
 
 /// Get red temporarily
@@ -26,15 +44,18 @@ pub struct Red(Timer);
 
 impl Default for Red {
     fn default() -> Self {
-        Self {
-            0: Timer::from_seconds(Self::DEFAULT_DURATION, TimerMode::Once),
-        }
+        Self::new(Self::DEFAULT_DURATION)
     }
 }
 
 impl Red {
     pub const DEFAULT_DURATION: f32 = 1.0;
     pub const N_BLINKS: usize = 6;
+
+    /// Blinks the same number of times over `secs`, e.g. to match an i-frame timer
+    pub fn new(secs: f32) -> Self {
+        Self(Timer::from_seconds(secs, TimerMode::Once))
+    }
 }
 
 // It could be efficient if this goes more discrete, not over frames, but that's only for such type of color trasition
@@ -46,7 +67,7 @@ fn update_red(
 ) {
     let d = time.delta();
     for (entity, mut sprite, mut red) in query {
-        let redness = (Red::DEFAULT_DURATION * 2.0 * (Red::N_BLINKS as f32) * red.0.elapsed_secs())
+        let redness = (2.0 * (Red::N_BLINKS as f32) * red.0.fraction())
             .cos()
             .signum()
             / 4.0
@@ -6,57 +6,80 @@
 //! - Set [`MovementController`] intent based on directional keyboard input.
 //!   This is done in the `player` module, as it is specific to the player
 //!   character.
-//! - Apply movement based on [`MovementController`] intent and maximum speed.
+//! - Accelerate toward the [`MovementController`] intent times the maximum
+//!   speed, in the fixed timestep.
+//! - Kinematic bodies collide and slide: the collider is shape cast along the
+//!   motion and whatever is left after a hit follows the surface.
 //! - Wrap the character within the window.
-//!
-//! Note that the implementation used here is limited for demonstration
-//! purposes. If you want to move the player in a smoother way,
-//! consider using a [fixed timestep](https://github.com/bevyengine/bevy/blob/main/examples/movement/physics_in_fixed_timestep.rs).
 use crate::{
     AppSystems, PausableSystems,
     audio::sound_effect,
     game::{
         Red,
+        aim::StickAim,
         animation::*,
+        dash::{Dash, Dashing},
         level::{
-            bosses::{
-                Boss, BossIntroPlaying, PHASE_1_NAME, PHASE_2_NAME, PHASE_3_NAME,
-                TUTORIAL_BOSS_NAME,
-            },
+            boss_definition::{BossDefinition, BossSource},
+            boss_phases::PhaseTransition,
+            bosses::BossIntroPlaying,
             enemies::*,
+            projectile_fx::despawn_with_impact,
             projectiles::*,
         },
         player::*,
+        rng::GameRng,
+        weapon::{Arsenal, Recall},
     },
+    input::{Action, TouchControls, action_just_pressed, action_just_released},
     screens::Screen,
-    ui::dialogue::DialogueQueue,
+    ui::{dialogue::DialogueQueue, menus::Menu},
+    utils::collisions_layers::GameLayer,
 };
 use avian2d::prelude::*;
-use bevy::{ecs::system::SystemParam, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{ecs::system::SystemParam, prelude::*};
 use rand::seq::IndexedRandom;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            apply_player_movement,
             apply_screen_wrap,
-            apply_player_throw.run_if(
-                input_just_pressed(MouseButton::Left)
-                    .and(in_state(Screen::Gameplay))
-                    .and(not(resource_exists::<DialogueQueue>))
-                    .and(not(any_with_component::<BossIntroPlaying>)),
-            ),
+            (
+                start_player_charge.run_if(action_just_pressed(Action::Throw)),
+                recall_projectiles.run_if(action_just_pressed(Action::Recall)),
+                update_player_charge,
+                apply_player_throw.run_if(action_just_released(Action::Throw)),
+            )
+                .chain()
+                .run_if(
+                    in_state(Screen::Gameplay)
+                        .and(not(resource_exists::<DialogueQueue>))
+                        .and(not(any_with_component::<BossIntroPlaying>)),
+                ),
         )
             .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
-    app.add_systems(FixedUpdate, (on_collision).in_set(PausableSystems));
+    // Whatever kept the throw from going off, the charge ends with the release
+    app.add_systems(
+        Update,
+        drop_charge
+            .run_if(action_just_released(Action::Throw))
+            .after(apply_player_throw),
+    );
+    app.add_systems(OnExit(Menu::None), drop_charge);
+    app.add_systems(Update, draw_charge_ring.run_if(in_state(Screen::Gameplay)));
+    app.add_systems(
+        FixedUpdate,
+        (apply_movement, on_collision, apply_piercing_hits).in_set(PausableSystems),
+    );
 }
 
 /// These are the movement parameters for our character controller.
-/// For now, this is only used for a single player, but it could power NPCs or
-/// other players as well.
+/// Anything with a [`RigidBody::Kinematic`] and a [`Collider`] can use it, the
+/// player and NPCs alike. A dynamic body gets the velocity and leaves the
+/// rest to the physics.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct MovementController {
@@ -66,6 +89,15 @@ pub struct MovementController {
     /// Maximum speed in world units per second.
     /// 1 world unit = 1 pixel when using the default 2D camera and no physics engine.
     pub max_speed: f32,
+
+    /// World units per second squared while speeding up or turning
+    pub acceleration: f32,
+
+    /// World units per second squared while slowing down without intent
+    pub deceleration: f32,
+
+    /// Current velocity, in world units per second
+    pub velocity: Vec2,
 }
 
 impl Default for MovementController {
@@ -73,36 +105,59 @@ impl Default for MovementController {
         Self {
             intent: Vec2::ZERO,
             max_speed: 1.0,
+            acceleration: 1800.0,
+            deceleration: 2400.0,
+            velocity: Vec2::ZERO,
         }
     }
 }
 
+/// Gap kept between a kinematic character and what it slides along
+const SKIN: f32 = 0.5;
+/// Surfaces one step of collide-and-slide may follow, enough for a corner
+const MAX_SLIDES: usize = 4;
+
 #[derive(SystemParam)]
 pub struct PassthroughHook<'w, 's> {
     projectile_query: Query<'w, 's, &'static Projectile>,
     passthrough_query: Query<'w, 's, &'static ProjectilePassthrough>,
-    recalled_query: Query<'w, 's, &'static Recalled>,
+    recalled_query: Query<'w, 's, (&'static RecallState, Option<&'static Damage>), With<Recalled>>,
     player_query: Query<'w, 's, &'static Player>,
+    dodging_query: Query<'w, 's, &'static Invulnerable, With<Player>>,
+    piercing_query: Query<'w, 's, (), With<Piercing>>,
+    hostile_query: Query<'w, 's, (), With<Hostile>>,
+    enemy_query: Query<'w, 's, &'static Enemy>,
+    recall_settings: Res<'w, RecallSettings>,
 }
 
 impl CollisionHooks for PassthroughHook<'_, '_> {
     fn filter_pairs(&self, collider1: Entity, collider2: Entity, _commands: &mut Commands) -> bool {
         // Recalled Projectile
-        let is_c1_recalled = self.recalled_query.contains(collider1);
-        let is_c2_recalled = self.recalled_query.contains(collider2);
-
-        if is_c1_recalled || is_c2_recalled {
-            let is_c1_player = self.player_query.contains(collider1);
-            let is_c2_player = self.player_query.contains(collider2);
+        let recalled = self
+            .recalled_query
+            .get(collider1)
+            .map(|recalled| (recalled, collider2))
+            .or_else(|_| {
+                self.recalled_query
+                    .get(collider2)
+                    .map(|recalled| (recalled, collider1))
+            });
 
-            // If one entity is Recalled, the OTHER must be the player to collide.
-            // Otherwise, ignore the collision entirely.
-            if is_c1_recalled && !is_c2_player {
-                return false;
+        if let Ok(((state, damage), other)) = recalled {
+            // The player always catches it
+            if self.player_query.contains(other) {
+                return true;
+            }
+            // Enemies are hit once each on the way back, if at all
+            if self.enemy_query.contains(other) {
+                return damage.is_none_or(|d| d.0 > 0) && !state.hits.contains(&other);
             }
-            if is_c2_recalled && !is_c1_player {
+            if self.projectile_query.contains(other) || self.passthrough_query.contains(other) {
                 return false;
             }
+            // Walls
+            return self.recall_settings.bounce_walls
+                && state.secs < self.recall_settings.ghost_after_secs;
         }
 
         let is_projectile1 = self.projectile_query.get(collider1).is_ok();
@@ -112,24 +167,39 @@ impl CollisionHooks for PassthroughHook<'_, '_> {
             return true;
         }
 
-        let (_projectile, passthrough) = if is_projectile1 {
+        let (projectile, other) = if is_projectile1 {
             (collider1, collider2)
         } else {
             (collider2, collider1)
         };
-        self.passthrough_query.get(passthrough).is_err()
+        // Dashing through bullets
+        if self
+            .dodging_query
+            .get(other)
+            .is_ok_and(Invulnerable::is_dodging)
+            && self.hostile_query.contains(projectile)
+        {
+            return false;
+        }
+        // Piercing shots go through enemies, `apply_piercing_hits` hurts them
+        if self.piercing_query.contains(projectile) && self.enemy_query.contains(other) {
+            return false;
+        }
+        self.passthrough_query.get(other).is_err()
     }
 }
 
-/// Collision handling; runs in FixedUpdate. [`restore_ammo`] must run after this
-/// so ammo is restored when friendly projectiles are despawned on hit.
+/// Collision handling; runs in FixedUpdate. Ammo is restored by an observer
+/// when friendly projectiles are despawned on hit.
 #[cfg_attr(any(), rustfmt::skip)]
 pub(crate) fn on_collision(
     mut commands: Commands,
     anim_assets: Res<AnimationAssets>,
+    mut rng: ResMut<GameRng>,
+    definitions: Res<Assets<BossDefinition>>,
     mut collision_reader: MessageReader<CollisionStart>,
-    mut enemy_query: Query<(Entity, &mut Enemy, Option<&Boss>, Option<&Name>)>,
-    mut player_query: Query<(Entity, &mut Player)>,
+    mut enemy_query: Query<(Entity, &mut Enemy, Option<&BossSource>, Has<PhaseTransition>)>,
+    mut player_query: Query<(Entity, &mut Player, &mut Invulnerable)>,
     mut projectile_query: Query<(
         Entity,
         &mut Projectile,
@@ -137,6 +207,9 @@ pub(crate) fn on_collision(
         &mut LinearVelocity,
         Has<Friendly>,
         Has<Hostile>,
+        Option<&Damage>,
+        Has<Piercing>,
+        Has<Recalled>,
     )>,
     mut something_else_query: Query<&ProjectilePassthrough>,
 ) {
@@ -148,10 +221,10 @@ pub(crate) fn on_collision(
         let mut is_c2_projectile: Option<bool> = None;
 
         // player/enemy with projectile
-        if on_collision_player(&mut commands, &anim_assets, &mut enemy_query, &mut player_query, &mut projectile_query, &mut something_else_query, &c1, &c2, &mut is_c2_projectile)
-        || on_collision_player(&mut commands, &anim_assets, &mut enemy_query, &mut player_query, &mut projectile_query, &mut something_else_query, &c2, &c1, &mut is_c1_projectile)
-        || on_collision_enemy(&mut commands, &anim_assets, &mut enemy_query, &mut player_query, &mut projectile_query,  &mut something_else_query, &c1, &c2, &mut is_c2_projectile)
-        || on_collision_enemy(&mut commands, &anim_assets, &mut enemy_query, &mut player_query, &mut projectile_query,  &mut something_else_query, &c2, &c1, &mut is_c1_projectile)
+        if on_collision_player(&mut commands, &anim_assets, &mut rng, &mut enemy_query, &mut player_query, &mut projectile_query, &mut something_else_query, &c1, &c2, &mut is_c2_projectile)
+        || on_collision_player(&mut commands, &anim_assets, &mut rng, &mut enemy_query, &mut player_query, &mut projectile_query, &mut something_else_query, &c2, &c1, &mut is_c1_projectile)
+        || on_collision_enemy(&mut commands, &anim_assets, &mut rng, &definitions, &mut enemy_query, &mut player_query, &mut projectile_query,  &mut something_else_query, &c1, &c2, &mut is_c2_projectile)
+        || on_collision_enemy(&mut commands, &anim_assets, &mut rng, &definitions, &mut enemy_query, &mut player_query, &mut projectile_query,  &mut something_else_query, &c2, &c1, &mut is_c1_projectile)
         {
             continue;
         }
@@ -160,7 +233,7 @@ pub(crate) fn on_collision(
             is_c1_projectile.unwrap_or(projectile_query.contains(c1)),
             is_c2_projectile.unwrap_or(projectile_query.contains(c2)),
         ) {
-            (true, true) => {commands.spawn(sound_effect(anim_assets.projectiles.ricochet.choose(&mut rand::rng()).unwrap().clone()));},
+            (true, true) => {commands.spawn(sound_effect(anim_assets.projectiles.ricochet.choose(rng.cosmetic()).unwrap().clone()));},
             (true, false) => on_collision_projectile_with_something_else(&mut commands, &anim_assets, &mut projectile_query, &mut something_else_query, &c1, &c2),
             (false, true) =>  on_collision_projectile_with_something_else(&mut commands, &anim_assets, &mut projectile_query, &mut something_else_query, &c2, &c1),
             (false, false) => {/* else vs else */}
@@ -172,8 +245,14 @@ pub(crate) fn on_collision(
 fn on_collision_player(
     commands: &mut Commands,
     anim_assets: &Res<AnimationAssets>,
-    enemy_query: &mut Query<(Entity, &mut Enemy, Option<&Boss>, Option<&Name>)>,
-    player_query: &mut Query<(Entity, &mut Player)>,
+    rng: &mut GameRng,
+    enemy_query: &mut Query<(
+        Entity,
+        &mut Enemy,
+        Option<&BossSource>,
+        Has<PhaseTransition>,
+    )>,
+    player_query: &mut Query<(Entity, &mut Player, &mut Invulnerable)>,
     projectile_query: &mut Query<(
         Entity,
         &mut Projectile,
@@ -181,6 +260,9 @@ fn on_collision_player(
         &mut LinearVelocity,
         Has<Friendly>,
         Has<Hostile>,
+        Option<&Damage>,
+        Has<Piercing>,
+        Has<Recalled>,
     )>,
     something_else_query: &mut Query<&ProjectilePassthrough>,
     c1: &Entity,
@@ -188,21 +270,36 @@ fn on_collision_player(
     is_c2_projectile: &mut Option<bool>,
 ) -> bool {
     // c1 is player and c2 is projectile
-    if let Ok((player_entity, mut player)) = player_query.get_mut(*c1) {
-        if let Ok((proj_entity, _, _, _, _, has_hostile)) = projectile_query.get(*c2) {
-            if has_hostile {
-                commands.entity(player_entity).insert(Red::default());
-                player.life = player.life.saturating_sub(1);
+    if let Ok((player_entity, mut player, mut invulnerable)) = player_query.get_mut(*c1) {
+        if let Ok((proj_entity, _, _, _, _, has_hostile, _, _, _)) = projectile_query.get(*c2) {
+            // The collision hook lets bullets through while dodging, this
+            // catches any contact that started before the dash
+            if has_hostile && invulnerable.is_dodging() {
+                *is_c2_projectile = Some(true);
+                return true;
+            }
+            if has_hostile && invulnerable.is_active() {
+                if invulnerable.shrug() {
+                    commands
+                        .spawn(sound_effect(anim_assets.player.shielded.clone()))
+                        .insert(PlaybackSettings::DESPAWN.with_speed(1.8));
+                }
+            } else if has_hostile {
+                invulnerable.start();
+                commands
+                    .entity(player_entity)
+                    .insert(Red::new(invulnerable.duration));
+                player.hurt(1);
                 commands.spawn(sound_effect(
                     anim_assets
                         .player
                         .damages
-                        .choose(&mut rand::rng())
+                        .choose(rng.cosmetic())
                         .unwrap()
                         .clone(),
                 ));
             }
-            commands.entity(proj_entity).despawn();
+            despawn_with_impact(commands, proj_entity);
             *is_c2_projectile = Some(true);
         } else {
             *is_c2_projectile = Some(false);
@@ -216,8 +313,15 @@ fn on_collision_player(
 fn on_collision_enemy(
     commands: &mut Commands,
     anim_assets: &Res<AnimationAssets>,
-    enemy_query: &mut Query<(Entity, &mut Enemy, Option<&Boss>, Option<&Name>)>,
-    player_query: &mut Query<(Entity, &mut Player)>,
+    rng: &mut GameRng,
+    definitions: &Res<Assets<BossDefinition>>,
+    enemy_query: &mut Query<(
+        Entity,
+        &mut Enemy,
+        Option<&BossSource>,
+        Has<PhaseTransition>,
+    )>,
+    player_query: &mut Query<(Entity, &mut Player, &mut Invulnerable)>,
     projectile_query: &mut Query<(
         Entity,
         &mut Projectile,
@@ -225,6 +329,9 @@ fn on_collision_enemy(
         &mut LinearVelocity,
         Has<Friendly>,
         Has<Hostile>,
+        Option<&Damage>,
+        Has<Piercing>,
+        Has<Recalled>,
     )>,
     something_else_query: &mut Query<&ProjectilePassthrough>,
     c1: &Entity,
@@ -232,68 +339,36 @@ fn on_collision_enemy(
     is_c2_projectile: &mut Option<bool>,
 ) -> bool {
     // c1 is enemy and c2 is projectile
-    if let Ok((enemy_entity, mut enemy, opt_boss, opt_name)) = enemy_query.get_mut(*c1) {
-        if let Ok((proj_entity, _, _, _, has_friendly, _)) = projectile_query.get(*c2) {
-            if has_friendly {
+    if let Ok((enemy_entity, mut enemy, opt_source, is_changing_phase)) = enemy_query.get_mut(*c1) {
+        if let Ok((proj_entity, _, _, _, has_friendly, _, damage, is_piercing, is_recalled)) =
+            projectile_query.get(*c2)
+        {
+            if has_friendly && is_changing_phase {
+                // Shrugs it off between phases
+                if !is_recalled && !is_piercing {
+                    despawn_with_impact(commands, proj_entity);
+                }
+            } else if has_friendly {
                 // Enemy got hit!
-                enemy.life = enemy.life.saturating_sub(1);
-                commands.entity(proj_entity).despawn();
-                commands.entity(enemy_entity).insert(Red::default());
-                if let (Some(_boss), Some(name)) = (opt_boss, opt_name) {
-                    match name.as_str() {
-                        TUTORIAL_BOSS_NAME => commands.spawn(sound_effect(
-                            anim_assets
-                                .enemies
-                                .eye_enemy
-                                .damages
-                                .choose(&mut rand::rng())
-                                .unwrap()
-                                .clone(),
-                        )),
-                        PHASE_1_NAME => commands.spawn(sound_effect(
-                            anim_assets
-                                .enemies
-                                .phase1
-                                .damages
-                                .choose(&mut rand::rng())
-                                .unwrap()
-                                .clone(),
-                        )),
-                        PHASE_2_NAME => commands.spawn(sound_effect(
-                            anim_assets
-                                .enemies
-                                .phase2
-                                .damages
-                                .choose(&mut rand::rng())
-                                .unwrap()
-                                .clone(),
-                        )),
-                        PHASE_3_NAME => commands.spawn(sound_effect(
-                            anim_assets
-                                .enemies
-                                .phase3
-                                .damages
-                                .choose(&mut rand::rng())
-                                .unwrap()
-                                .clone(),
-                        )),
-
-                        _ => {
-                            panic!("unknown boss")
-                        }
-                    };
-                } else {
-                    // non-boss
-                    commands.spawn(sound_effect(
-                        anim_assets
-                            .enemies
-                            .eye_enemy
-                            .damages
-                            .choose(&mut rand::rng())
-                            .unwrap()
-                            .clone(),
-                    ));
+                if is_recalled {
+                    // Keep coming back, but don't hit this one again
+                    commands
+                        .entity(proj_entity)
+                        .entry::<RecallState>()
+                        .and_modify(move |mut state| state.hits.push(enemy_entity));
+                } else if !is_piercing {
+                    despawn_with_impact(commands, proj_entity);
                 }
+                hurt_enemy(
+                    commands,
+                    anim_assets,
+                    rng,
+                    definitions,
+                    enemy_entity,
+                    &mut enemy,
+                    opt_source,
+                    damage.map_or(1, |d| d.0),
+                );
             }
             // nothing for enemy bullet to enemy(drain)
             *is_c2_projectile = Some(true);
@@ -306,6 +381,90 @@ fn on_collision_enemy(
     // NOTE: nothing for enemy-to-enemy collision
 }
 
+/// Takes `damage` lives, flashes the enemy red and plays its hurt sound
+fn hurt_enemy(
+    commands: &mut Commands,
+    anim_assets: &AnimationAssets,
+    rng: &mut GameRng,
+    definitions: &Assets<BossDefinition>,
+    enemy_entity: Entity,
+    enemy: &mut Enemy,
+    opt_source: Option<&BossSource>,
+    damage: usize,
+) {
+    enemy.life = enemy.life.saturating_sub(damage);
+    commands.entity(enemy_entity).insert(Red::default());
+    // Bosses sound the way their definition says
+    let sound = opt_source
+        .and_then(|source| definitions.get(&source.definition))
+        .and_then(|definition| definition.damage_sounds.choose(rng.cosmetic()).cloned())
+        .or_else(|| {
+            anim_assets
+                .enemies
+                .eye_enemy
+                .damages
+                .choose(rng.cosmetic())
+                .cloned()
+        });
+    if let Some(sound) = sound {
+        commands.spawn(sound_effect(sound));
+    }
+}
+
+/// Piercing shots have no contact with enemies (see [`PassthroughHook`]), so
+/// their hits are found by overlap instead. Each enemy is hit once per shot.
+fn apply_piercing_hits(
+    mut commands: Commands,
+    anim_assets: Res<AnimationAssets>,
+    mut rng: ResMut<GameRng>,
+    definitions: Res<Assets<BossDefinition>>,
+    spatial_query: SpatialQuery,
+    mut projectile_query: Query<
+        (
+            &mut Piercing,
+            &Collider,
+            &Position,
+            &Rotation,
+            Option<&Damage>,
+        ),
+        (With<Friendly>, Without<Recalled>),
+    >,
+    mut enemy_query: Query<(&mut Enemy, Option<&BossSource>, Has<PhaseTransition>)>,
+) {
+    for (mut piercing, collider, position, rotation, damage) in &mut projectile_query {
+        let overlaps = spatial_query.shape_intersections(
+            collider,
+            position.0,
+            rotation.as_radians(),
+            &SpatialQueryFilter::default(),
+        );
+        for enemy_entity in overlaps {
+            if piercing.hits.contains(&enemy_entity) {
+                continue;
+            }
+            let Ok((mut enemy, opt_source, is_changing_phase)) = enemy_query.get_mut(enemy_entity)
+            else {
+                continue;
+            };
+            piercing.hits.push(enemy_entity);
+            // Shrugs it off between phases
+            if is_changing_phase {
+                continue;
+            }
+            hurt_enemy(
+                &mut commands,
+                &anim_assets,
+                &mut rng,
+                &definitions,
+                enemy_entity,
+                &mut enemy,
+                opt_source,
+                damage.map_or(1, |d| d.0),
+            );
+        }
+    }
+}
+
 fn on_collision_projectile_with_something_else(
     commands: &mut Commands,
     anim_assets: &Res<AnimationAssets>,
@@ -316,17 +475,33 @@ fn on_collision_projectile_with_something_else(
         &mut LinearVelocity,
         Has<Friendly>,
         Has<Hostile>,
+        Option<&Damage>,
+        Has<Piercing>,
+        Has<Recalled>,
     )>,
     something_else_query: &mut Query<&ProjectilePassthrough>,
     c1: &Entity,
     c2: &Entity,
 ) {
-    if let Ok((proj_entity, mut projectile, mut transform, mut velocity, has_friendly, _)) =
-        projectile_query.get_mut(*c1)
+    if let Ok((
+        proj_entity,
+        mut projectile,
+        mut transform,
+        mut velocity,
+        has_friendly,
+        _,
+        _,
+        _,
+        is_recalled,
+    )) = projectile_query.get_mut(*c1)
     {
         if let Ok(projectile_passthrough) = something_else_query.get_mut(*c2) {
             // nothing (replaced with hook)
         }
+        if is_recalled {
+            // Bouncing off a wall on the way back. Homing takes it from here.
+            return;
+        }
         if has_friendly {
             for due in projectile.dues.iter_mut() {
                 match due {
@@ -339,7 +514,7 @@ fn on_collision_projectile_with_something_else(
                             }
                             0 => {
                                 //panic!("Bounce Down was not set correctly");
-                                commands.entity(proj_entity).despawn(); // should not happen
+                                despawn_with_impact(commands, proj_entity); // should not happen
                             }
                             _ => {
                                 *count = count.saturating_sub(1);
@@ -350,37 +525,238 @@ fn on_collision_projectile_with_something_else(
                 }
             }
         } else {
-            commands.entity(proj_entity).despawn();
+            despawn_with_impact(commands, proj_entity);
+        }
+    }
+}
+
+fn apply_movement(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    passable: Query<(), Or<(With<Projectile>, With<Sensor>)>>,
+    mut movement_query: Query<(
+        Entity,
+        &mut MovementController,
+        &mut LinearVelocity,
+        &RigidBody,
+        &Collider,
+        &Position,
+        Option<&Dash>,
+        Option<&Dashing>,
+    )>,
+) {
+    let dt = time.delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+    for (entity, mut controller, mut rb_vel, body, collider, position, dash, dashing) in
+        movement_query.iter_mut()
+    {
+        controller.velocity = match (dash, dashing) {
+            // A dash is at full speed right away
+            (Some(dash), Some(dashing)) => dash.speed() * dashing.direction,
+            _ => {
+                let target = controller.max_speed * controller.intent;
+                let rate = if target == Vec2::ZERO {
+                    controller.deceleration
+                } else {
+                    controller.acceleration
+                };
+                controller.velocity.move_towards(target, rate * dt)
+            }
+        };
+        if !body.is_kinematic() {
+            rb_vel.0 = controller.velocity;
+            continue;
+        }
+
+        // Walls, props and other bodies stop the character, bullets and
+        // pickups are for the collision handling.
+        let filter =
+            SpatialQueryFilter::from_mask(GameLayer::Walls).with_excluded_entities([entity]);
+        let (motion, blocked) = collide_and_slide(
+            &spatial_query,
+            collider,
+            position.0,
+            controller.velocity * dt,
+            &filter,
+            &|hit| !passable.contains(hit),
+        );
+        // No pushing into a wall, only along it
+        for normal in blocked {
+            let into = controller.velocity.dot(normal);
+            if into < 0.0 {
+                controller.velocity -= into * normal;
+            }
+        }
+        rb_vel.0 = motion / dt;
+    }
+}
+
+/// How far `collider` at `origin` gets along `motion`. After each hit the
+/// rest of the motion is projected onto the surface, so the character slides
+/// along walls and around corners instead of snagging. Also returns the
+/// normals of the surfaces it hit.
+fn collide_and_slide(
+    spatial_query: &SpatialQuery,
+    collider: &Collider,
+    origin: Vec2,
+    mut motion: Vec2,
+    filter: &SpatialQueryFilter,
+    is_solid: &dyn Fn(Entity) -> bool,
+) -> (Vec2, Vec<Vec2>) {
+    let mut position = origin;
+    let mut normals = Vec::new();
+    for _ in 0..MAX_SLIDES {
+        let Ok((direction, distance)) = Dir2::new_and_length(motion) else {
+            break;
+        };
+        let config = ShapeCastConfig {
+            // Already overlapping something must not pin the character there
+            ignore_origin_penetration: true,
+            ..ShapeCastConfig::from_max_distance(distance + SKIN)
+        };
+        let Some(hit) = spatial_query.cast_shape_predicate(
+            collider, position, 0.0, direction, &config, filter, is_solid,
+        ) else {
+            position += motion;
+            break;
+        };
+        let travel = (hit.distance - SKIN).clamp(0.0, distance);
+        position += direction * travel;
+        let rest = direction * (distance - travel);
+        motion = rest - rest.dot(hit.normal1) * hit.normal1;
+        normals.push(hit.normal1);
+    }
+    (position - origin, normals)
+}
+
+/// Hold-to-power-up state of the throw. Inserted on press and consumed on release.
+#[derive(Component, Debug, Default)]
+pub struct Charge {
+    pub secs: f32,
+}
+
+impl Charge {
+    /// Seconds of holding for a full charge
+    pub const FULL_SECS: f32 = 1.0;
+    pub const RING_RADIUS: f32 = 20.0;
+
+    pub fn power(&self) -> f32 {
+        (self.secs / Self::FULL_SECS).min(1.0)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.secs >= Self::FULL_SECS
+    }
+}
+
+/// Start charging if the current weapon can be thrown, recall otherwise
+fn start_player_charge(
+    mut commands: Commands,
+    anim_assets: Res<AnimationAssets>,
+    player: Single<(Entity, &Player, &Arsenal), With<Cool>>,
+    projectiles: Query<(Entity, &Recall), (With<Projectile>, With<Friendly>, Without<Recalled>)>,
+) {
+    let (player_entity, player, arsenal) = player.into_inner();
+
+    if player.ammo >= arsenal.current().ammo_cost {
+        commands.entity(player_entity).insert(Charge::default());
+        commands.spawn(sound_effect(anim_assets.player.charge.clone()));
+    } else {
+        recall_manual(&mut commands, &projectiles);
+    }
+}
+
+/// [`Action::Recall`] calls them back whether there is ammo left or not
+fn recall_projectiles(
+    mut commands: Commands,
+    projectiles: Query<(Entity, &Recall), (With<Projectile>, With<Friendly>, Without<Recalled>)>,
+) {
+    recall_manual(&mut commands, &projectiles);
+}
+
+fn recall_manual(
+    commands: &mut Commands,
+    projectiles: &Query<(Entity, &Recall), (With<Projectile>, With<Friendly>, Without<Recalled>)>,
+) {
+    for (entity, recall) in projectiles {
+        if *recall == Recall::Manual {
+            commands.entity(entity).insert(Recalled);
         }
     }
 }
 
-fn apply_player_movement(mut movement_query: Query<(&MovementController, &mut LinearVelocity)>) {
-    for (controller, mut rb_vel) in movement_query.iter_mut() {
-        rb_vel.0 = controller.max_speed * controller.intent; // normal
+fn update_player_charge(
+    mut commands: Commands,
+    time: Res<Time>,
+    anim_assets: Res<AnimationAssets>,
+    mut rng: ResMut<GameRng>,
+    mut query: Query<&mut Charge, With<Player>>,
+) {
+    for mut charge in &mut query {
+        let was_full = charge.is_full();
+        charge.secs += time.delta_secs();
+        if !was_full && charge.is_full() {
+            commands.spawn(sound_effect(
+                anim_assets
+                    .projectiles
+                    .ricochet
+                    .choose(rng.cosmetic())
+                    .unwrap()
+                    .clone(),
+            ));
+        }
     }
 }
 
+fn drop_charge(mut commands: Commands, query: Query<Entity, (With<Player>, With<Charge>)>) {
+    for entity in &query {
+        commands.entity(entity).remove::<Charge>();
+    }
+}
+
+fn draw_charge_ring(mut gizmos: Gizmos, query: Query<(&GlobalTransform, &Charge), With<Player>>) {
+    for (transform, charge) in &query {
+        let center = transform.translation().xy();
+        let power = charge.power();
+        let color = if charge.is_full() {
+            Color::srgb(1.0, 0.753, 0.0)
+        } else {
+            Color::srgba(1.0, 1.0, 1.0, 0.4 + 0.6 * power)
+        };
+        // The ring closes in on the player as the charge builds up
+        gizmos.circle_2d(center, Charge::RING_RADIUS * (2.0 - power), color);
+    }
+}
+
+/// Throw on release, powered by how long the button was held
 fn apply_player_throw(
     mut commands: Commands,
     anim_assets: Res<AnimationAssets>,
-    player: Single<(Entity, &Transform, &mut Player), With<Cool>>,
+    mut rng: ResMut<GameRng>,
+    player: Single<(Entity, &Transform, &mut Player, &Arsenal, &Charge), With<Cool>>,
     global_transform: Query<&GlobalTransform>,
     camera_query: Single<(&Camera, &GlobalTransform)>,
-    projectiles: Query<Entity, (With<Projectile>, With<Friendly>, Without<Recalled>)>,
     window: Single<&Window>,
+    stick_aim: Res<StickAim>,
+    touch: Res<TouchControls>,
 ) {
-    let (player_entity, player_transform, mut player) = player.into_inner();
+    let (player_entity, player_transform, mut player, arsenal, charge) = player.into_inner();
+    let weapon = arsenal.current();
+    commands.entity(player_entity).remove::<Charge>();
 
-    if player.ammo != 0
+    if player.ammo >= weapon.ammo_cost
         && let Ok(player_global_transform) = global_transform.get(player_entity)
     {
         let (x, y, _) = player_global_transform.translation().into(); // This may differ by the worldwrap
         let xy = Vec2::new(x, y);
 
         let (camera, camera_transform) = *camera_query;
-        let dir_not_norm = if let Some(cursor_position) = window.cursor_position()
-               // Calculate a world position based on the cursor's position.
+        let dir_not_norm = if let Some(direction) = stick_aim.0 {
+            direction.as_vec2()
+        } else if let Some(cursor_position) = touch.tap().or(window.cursor_position())
+               // Calculate a world position based on the cursor's (or tap's) position.
                && let Ok(cursor_world_pos) = camera.viewport_to_world_2d(camera_transform, cursor_position)
         {
             cursor_world_pos - xy
@@ -390,32 +766,25 @@ fn apply_player_throw(
         };
 
         let direction = Dir2::new(dir_not_norm.normalize()).expect("It is not normalized");
-        commands.spawn(player_chakra::<Friendly>(
-            xy,
-            direction,
-            PLAYER_COLLIDER_CAPSULE.0,
-            PLAYER_COLLIDER_CAPSULE.1,
-            &anim_assets,
+        let projectile = weapon.spawn_projectile(&mut commands, xy, direction, &anim_assets);
+        commands.entity(projectile).insert((
+            ChargedShot(charge.power()),
+            ExtraBounces(player.extra_bounces),
         ));
         commands.spawn(sound_effect(
             anim_assets
                 .player
                 .attacks
-                .choose(&mut rand::rng())
+                .choose(rng.cosmetic())
                 .unwrap()
                 .clone(),
         ));
-        player.decrement_ammo(1);
+        player.decrement_ammo(weapon.ammo_cost);
 
         // update cool
         commands.entity(player_entity).remove::<Cool>();
-        commands.spawn(Cool::new(player.cool));
-    } else {
-        for entity in &projectiles {
-            commands.entity(entity).insert(Recalled);
-        }
+        commands.spawn(Cool::new(player.throw_cool() + weapon.cooldown));
     }
-    // if ammo is out nah.
 }
 
 #[derive(Component, Reflect)]
@@ -7,12 +7,18 @@ use crate::{
     AppSystems, PausableSystems,
     asset_tracking::LoadResource,
     game::{
-        animation::{AnimationAssets, PlayerAnimation, PlayerAnimationState},
+        animation::{
+            AnimationAssets, PLAYER_ANIMATION_SPEED, PlayerAnimation, PlayerAnimationState,
+        },
+        dash::{Dash, Dashing},
         level::{bosses::BossIntroPlaying, projectiles::*},
         movement::{MovementController, ScreenWrap},
+        weapon::Arsenal,
     },
+    input::ActionInput,
     screens::Screen,
     ui::dialogue::DialogueQueue,
+    utils::collisions_layers::player_layers,
 };
 
 pub const PLAYER_Z_TRANSLATION: f32 = 100.;
@@ -34,6 +40,10 @@ pub(super) fn plugin(app: &mut App) {
                     .and(not(any_with_component::<BossIntroPlaying>)),
             ),
     );
+    app.add_systems(
+        Update,
+        (tick_player_buffs, tick_invulnerability).in_set(PausableSystems),
+    );
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
@@ -45,6 +55,11 @@ pub struct Player {
     pub max_life: usize,
     pub life: usize,
     pub cool: f32, // throw cool time. every [`cool`] second, the player can throw
+    pub coins: usize,
+    /// Seconds left of a haste buff, which cuts [`Player::cool`]
+    pub haste_secs: f32,
+    /// Wall bounces added to every throw, from upgrades
+    pub extra_bounces: usize,
 }
 
 impl Default for Player {
@@ -55,11 +70,17 @@ impl Default for Player {
             cool: 0.3,
             max_ammo: 3,
             ammo: 3,
+            coins: 0,
+            haste_secs: 0.0,
+            extra_bounces: 0,
         }
     }
 }
 
 impl Player {
+    /// [`Player::cool`] is multiplied by this while hasted
+    pub const HASTE_COOL_FACTOR: f32 = 0.5;
+
     pub fn increment_ammo(&mut self, n: usize) {
         self.ammo = (self.ammo + n).min(self.max_ammo);
     }
@@ -78,6 +99,113 @@ impl Player {
         // In case any later debug; Negative ammo should not happen
         self.ammo = self.ammo.saturating_sub(n);
     }
+
+    pub fn heal(&mut self, n: usize) {
+        self.life = (self.life + n).min(self.max_life);
+    }
+
+    /// Room for `n` more, filled right away
+    pub fn raise_max_ammo(&mut self, n: usize) {
+        self.max_ammo += n;
+        self.increment_ammo(n);
+    }
+
+    /// `n` more hearts, filled right away
+    pub fn raise_max_life(&mut self, n: usize) {
+        self.max_life += n;
+        self.heal(n);
+    }
+
+    pub fn hurt(&mut self, n: usize) {
+        self.life = self.life.saturating_sub(n);
+    }
+
+    pub fn add_coins(&mut self, n: usize) {
+        self.coins += n;
+    }
+
+    /// A new haste replaces what is left of the old one if it lasts longer
+    pub fn hasten(&mut self, secs: f32) {
+        self.haste_secs = self.haste_secs.max(secs);
+    }
+
+    pub fn is_hasted(&self) -> bool {
+        self.haste_secs > 0.0
+    }
+
+    /// Throw cool time with buffs applied
+    pub fn throw_cool(&self) -> f32 {
+        if self.is_hasted() {
+            self.cool * Self::HASTE_COOL_FACTOR
+        } else {
+            self.cool
+        }
+    }
+}
+
+/// Hits are shrugged off while the timer runs. Started by every hit that
+/// goes through, so a spread of bullets costs one life instead of several.
+/// A dash starts or extends it too, and bullets pass through a dodge instead.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Invulnerable {
+    /// Seconds it lasts after a hit
+    pub duration: f32,
+    timer: Timer,
+    /// Started by a dash rather than a hit
+    dodging: bool,
+    /// A shrugged off hit was heard already since the last start
+    shield_heard: bool,
+}
+
+impl Default for Invulnerable {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_DURATION)
+    }
+}
+
+impl Invulnerable {
+    pub const DEFAULT_DURATION: f32 = 1.0;
+
+    pub fn new(duration: f32) -> Self {
+        let mut timer = Timer::from_seconds(duration, TimerMode::Once);
+        // Not running until the first hit
+        timer.finish();
+        Self {
+            duration,
+            timer,
+            dodging: false,
+            shield_heard: false,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.timer.is_finished()
+    }
+
+    pub fn start(&mut self) {
+        self.timer = Timer::from_seconds(self.duration, TimerMode::Once);
+        self.dodging = false;
+        self.shield_heard = false;
+    }
+
+    /// Invulnerable for at least `secs` more, letting bullets through
+    pub fn dodge(&mut self, secs: f32) {
+        if self.timer.remaining_secs() < secs {
+            self.timer = Timer::from_seconds(secs, TimerMode::Once);
+        }
+        self.dodging = true;
+    }
+
+    pub fn is_dodging(&self) -> bool {
+        self.dodging && self.is_active()
+    }
+
+    /// Whether a shrugged off hit should play a sound, once per start so a
+    /// spread of bullets clinks once
+    pub fn shrug(&mut self) -> bool {
+        !std::mem::replace(&mut self.shield_heard, true)
+    }
 }
 
 /// The player character.
@@ -97,7 +225,7 @@ pub fn player(
             animation: Animation::tag("walk-up")
                 .with_repeat(AnimationRepeat::Loop)
                 .with_direction(AnimationDirection::Forward)
-                .with_speed(2.0),
+                .with_speed(PLAYER_ANIMATION_SPEED),
             aseprite: anim_assets.player.aseprite.clone(),
         },
         Sprite::default(),
@@ -106,54 +234,44 @@ pub fn player(
             ..default()
         },
         ScreenWrap,
-        LockedAxes::new().lock_rotation(), // To be resolved with later kinematic solution
         Transform::from_xyz(transform.x, transform.y, PLAYER_Z_TRANSLATION),
-        // TODO: possibly kinematic later that should update `movement::apply_movement` along
-        RigidBody::Dynamic,
-        GravityScale(0.0),
+        // Moved by `movement::apply_movement` only, bosses can't shove it around
+        RigidBody::Kinematic,
         Collider::capsule(PLAYER_COLLIDER_CAPSULE.0, PLAYER_COLLIDER_CAPSULE.1),
-        Cool::default(),
+        player_layers(),
+        (
+            Cool::default(),
+            Arsenal::default(),
+            Dash::default(),
+            Invulnerable::default(),
+        ),
     )
 }
 
 fn record_player_directional_input(
-    input: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     mut query: Query<
         (
             &mut MovementController,
             &mut PlayerAnimation,
             &mut Transform,
+            Has<Dashing>,
         ),
         With<Player>,
     >,
 ) {
-    for (mut controller, mut animation, mut transform) in &mut query {
-        let mut pressed_flag: bool = false;
-        // Collect directional input.
-        let mut intent = Vec2::ZERO;
-        if input.pressed(KeyCode::KeyW) {
-            intent.y += 1.0;
-            pressed_flag = true;
-        }
-        if input.pressed(KeyCode::KeyS) {
-            intent.y -= 1.0;
-            pressed_flag = true;
-        }
-        if input.pressed(KeyCode::KeyA) {
-            intent.x -= 1.0;
-            pressed_flag = true;
+    for (mut controller, mut animation, mut transform, is_dashing) in &mut query {
+        // Keyboard diagonals are normalized, a half tilted stick walks at half speed.
+        let intent = input.movement();
+        controller.intent = intent;
+        if intent.x < 0.0 {
             transform.scale.x = -1.;
-        }
-        if input.pressed(KeyCode::KeyD) {
-            intent.x += 1.0;
-            pressed_flag = true;
+        } else if intent.x > 0.0 {
             transform.scale.x = 1.;
         }
-        // Normalize intent so that diagonal movement is the same speed as horizontal / vertical.
-        // This should be omitted if the input comes from an analog stick instead.
-        let intent = intent.normalize_or_zero();
-        controller.intent = intent;
-        if pressed_flag {
+        if is_dashing {
+            animation.state = PlayerAnimationState::Dash;
+        } else if intent != Vec2::ZERO {
             animation.state = PlayerAnimationState::Walk;
         } else {
             animation.state = PlayerAnimationState::Idle;
@@ -161,6 +279,20 @@ fn record_player_directional_input(
     }
 }
 
+fn tick_invulnerability(time: Res<Time>, mut query: Query<&mut Invulnerable>) {
+    for mut invulnerable in &mut query {
+        invulnerable.timer.tick(time.delta());
+    }
+}
+
+fn tick_player_buffs(time: Res<Time>, mut query: Query<&mut Player>) {
+    for mut player in &mut query {
+        if player.is_hasted() {
+            player.haste_secs = (player.haste_secs - time.delta_secs()).max(0.0);
+        }
+    }
+}
+
 #[derive(Asset, Clone, Reflect)]
 pub struct PlayerAssets {
     pub aseprite: Handle<Aseprite>,
@@ -173,4 +305,9 @@ pub struct PlayerAssets {
     pub damages: Vec<Handle<AudioSource>>,
     #[dependency]
     pub pickup: Handle<AudioSource>,
+    #[dependency]
+    pub charge: Handle<AudioSource>,
+    /// A hit shrugged off while [`Invulnerable`]
+    #[dependency]
+    pub shielded: Handle<AudioSource>,
 }
@@ -0,0 +1,86 @@
+//! One seeded source for every roll the game makes, so a run can be replayed.
+//! [`GameRng`] keeps two streams: `gameplay` for anything that changes the
+//! outcome (enemy moves, bullet spread) and `cosmetic` for sounds and looks.
+//! Rolling a cosmetic value never shifts the gameplay stream.
+//!
+//! The seed is picked at startup: `--seed <n>` on the command line, then the
+//! `NARAK_SEED` environment variable, otherwise a random one.
+//! Both streams restart from the seed on every level start, so retrying a
+//! level with the same inputs plays out the same way.
+//!
+//! The streams are [`ChaCha8Rng`] rather than `StdRng`, whose algorithm may
+//! change between `rand` versions: a seed plays the same on every platform
+//! and build.
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{game::level::Level, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    let seed = seed_from_args().unwrap_or_else(rand::random);
+    info!("Game seed: {seed}");
+    app.insert_resource(GameRng::new(seed));
+    app.add_systems(OnEnter(Screen::Gameplay), reseed_for_level);
+}
+
+pub const SEED_ARG: &str = "--seed";
+pub const SEED_ENV: &str = "NARAK_SEED";
+
+// Any odd constant works; it only has to keep the two streams apart
+const COSMETIC_STREAM: u64 = 0x9E37_79B9_7F4A_7C15;
+
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    seed: u64,
+    gameplay: ChaCha8Rng,
+    cosmetic: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            gameplay: ChaCha8Rng::seed_from_u64(seed),
+            cosmetic: ChaCha8Rng::seed_from_u64(seed ^ COSMETIC_STREAM),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restart both streams from the seed mixed with `salt`
+    pub fn reseed(&mut self, salt: u64) {
+        let seed = self.seed.wrapping_add(salt);
+        self.gameplay = ChaCha8Rng::seed_from_u64(seed);
+        self.cosmetic = ChaCha8Rng::seed_from_u64(seed ^ COSMETIC_STREAM);
+    }
+
+    /// Rolls that change how the run plays out
+    pub fn gameplay(&mut self) -> &mut ChaCha8Rng {
+        &mut self.gameplay
+    }
+
+    /// Rolls for sounds and looks only
+    pub fn cosmetic(&mut self) -> &mut ChaCha8Rng {
+        &mut self.cosmetic
+    }
+}
+
+fn seed_from_args() -> Option<u64> {
+    let mut args = std::env::args().skip_while(|arg| arg != SEED_ARG).skip(1);
+    let arg = args.next().or_else(|| std::env::var(SEED_ENV).ok())?;
+    match arg.trim().parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => {
+            warn!("Ignoring invalid seed {arg:?}");
+            None
+        }
+    }
+}
+
+fn reseed_for_level(mut rng: ResMut<GameRng>, level: Res<State<Level>>) {
+    rng.reseed(*level.get() as u64);
+}
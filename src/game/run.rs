@@ -0,0 +1,163 @@
+//! What carries over from one level to the next within a run.
+//! A run starts fresh whenever the title screen shows.
+//!
+//! Upgrades always carry over. With [`RunMode`] on, so do the hearts and ammo
+//! the player is missing: the next level doesn't heal what the last one took.
+//! The maximums themselves are not carried on purpose. They come from the
+//! next level and the difficulty, plus the upgrades, like in a fresh start.
+
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    game::{
+        level::projectiles::{AmmoCost, Friendly},
+        movement::MovementController,
+        player::Player,
+        upgrades::Upgrade,
+    },
+    screens::{Screen, gameplay::StartLoadNext},
+    ui::menus::Menu,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunMode>();
+    app.init_resource::<RunState>();
+    app.add_systems(OnEnter(Screen::Title), reset_run_state);
+    app.add_systems(OnEnter(Screen::Gameplay), mark_level_start);
+    app.add_systems(
+        Update,
+        tick_run_time
+            .run_if(in_state(Screen::Gameplay).and(in_state(Menu::None)))
+            .in_set(PausableSystems),
+    );
+    app.add_observer(apply_run_state)
+        .add_observer(carry_player_stats);
+}
+
+/// Whether hearts and ammo carry over between levels
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct RunMode(pub bool);
+
+#[derive(Resource, Debug, Default)]
+pub struct RunState {
+    /// In the order they were picked
+    pub upgrades: Vec<Upgrade>,
+    /// How the player left the last cleared level, only kept in [`RunMode`]
+    pub carried: Option<CarriedStats>,
+    /// Seconds of gameplay since the run started, menus and pauses excluded
+    pub elapsed_secs: f32,
+    /// As the current level started, for a retry
+    level_start: Option<LevelStart>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CarriedStats {
+    pub life: usize,
+    pub max_life: usize,
+    pub ammo: usize,
+    pub max_ammo: usize,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LevelStart {
+    carried: Option<CarriedStats>,
+    elapsed_secs: f32,
+    upgrades: usize,
+}
+
+impl CarriedStats {
+    pub fn from_player(player: &Player) -> Self {
+        Self {
+            life: player.life,
+            max_life: player.max_life,
+            ammo: player.ammo,
+            max_ammo: player.max_ammo,
+        }
+    }
+
+    /// The new level sets the maximums, the player stays short of them by as
+    /// much as they were when the last level ended. Never below one heart.
+    /// `max_life` and `max_ammo` are only kept to know what was missing.
+    pub fn restore(&self, player: &mut Player) {
+        let missing_life = self.max_life.saturating_sub(self.life);
+        let missing_ammo = self.max_ammo.saturating_sub(self.ammo);
+        player.life = player.max_life.saturating_sub(missing_life).max(1);
+        player.ammo = player.max_ammo.saturating_sub(missing_ammo);
+    }
+}
+
+impl RunState {
+    pub fn count(&self, upgrade: Upgrade) -> usize {
+        self.upgrades.iter().filter(|u| **u == upgrade).count()
+    }
+
+    /// Seconds spent in the current level so far
+    pub fn level_secs(&self) -> f32 {
+        self.elapsed_secs - self.level_start.map_or(0.0, |start| start.elapsed_secs)
+    }
+
+    /// Back to how the current level started
+    pub fn retry_level(&mut self) {
+        if let Some(start) = self.level_start {
+            self.carried = start.carried;
+            self.elapsed_secs = start.elapsed_secs;
+            self.upgrades.truncate(start.upgrades);
+        }
+    }
+}
+
+fn reset_run_state(mut run: ResMut<RunState>) {
+    *run = RunState::default();
+}
+
+fn mark_level_start(mut run: ResMut<RunState>) {
+    run.level_start = Some(LevelStart {
+        carried: run.carried,
+        elapsed_secs: run.elapsed_secs,
+        upgrades: run.upgrades.len(),
+    });
+}
+
+fn tick_run_time(time: Res<Time>, mut run: ResMut<RunState>) {
+    run.elapsed_secs += time.delta_secs();
+}
+
+/// The fresh player of every level gets the upgrades of the run, then what it
+/// was missing at the end of the last level
+fn apply_run_state(
+    add: On<Add, Player>,
+    run: Res<RunState>,
+    mode: Res<RunMode>,
+    mut query: Query<(&mut Player, &mut MovementController)>,
+) {
+    let Ok((mut player, mut controller)) = query.get_mut(add.entity) else {
+        return;
+    };
+    for upgrade in &run.upgrades {
+        upgrade.apply(&mut player, &mut controller);
+    }
+    if mode.0
+        && let Some(carried) = &run.carried
+    {
+        carried.restore(&mut player);
+    }
+}
+
+/// The boss is down, remember how the player ends the level. Physics stopped
+/// with the boss, so the throws still in the air count as caught.
+fn carry_player_stats(
+    _: On<StartLoadNext>,
+    mut run: ResMut<RunState>,
+    mode: Res<RunMode>,
+    player: Option<Single<&Player>>,
+    in_flight: Query<Option<&AmmoCost>, With<Friendly>>,
+) {
+    let (true, Some(player)) = (mode.0, player) else {
+        return;
+    };
+    let mut player = **player;
+    player.increment_ammo(in_flight.iter().map(|cost| cost.map_or(1, |c| c.0)).sum());
+    run.carried = Some(CarriedStats::from_player(&player));
+}
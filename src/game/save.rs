@@ -0,0 +1,231 @@
+//! Progress and settings kept between launches, in `save.ron` next to the key
+//! bindings in the platform config directory.
+//!
+//! A save that can't be read, or was written for another [`SAVE_VERSION`], is
+//! left alone and the game starts from a fresh [`SaveData`]. The file is
+//! replaced the next time anything worth saving changes.
+
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        difficulty::Difficulty,
+        level::{Level, bosses::BossFlow},
+        run::{RunMode, RunState},
+    },
+    screens::gameplay::StartLoadNext,
+    utils::config::{read_config, write_config},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(SaveData::load());
+    app.add_systems(Startup, apply_saved_settings);
+    app.add_systems(
+        Update,
+        (
+            record_settings.run_if(
+                resource_changed::<GlobalVolume>
+                    .or(resource_changed::<Difficulty>)
+                    .or(resource_changed::<BossFlow>)
+                    .or(resource_changed::<RunMode>),
+            ),
+            write_save.run_if(resource_changed::<SaveData>.and(not(resource_added::<SaveData>))),
+        )
+            .chain(),
+    );
+    app.add_observer(record_level_clear);
+}
+
+const SAVE_FILE: &str = "save.ron";
+
+/// Bump whenever [`SaveData`] changes shape. Saves of any other version are
+/// ignored rather than half read.
+pub const SAVE_VERSION: u32 = 2;
+
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SaveData {
+    pub version: u32,
+    /// The furthest level the player has got to, where "Continue" starts
+    pub highest_level: Level,
+    pub settings: SavedSettings,
+    /// Fastest clears, pauses and menus excluded
+    pub best_times: Vec<BestTime>,
+    pub unlocks: Vec<Unlock>,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            highest_level: Level::default(),
+            settings: SavedSettings::default(),
+            best_times: Vec::new(),
+            unlocks: Vec::new(),
+        }
+    }
+}
+
+/// Only the version, readable from a save of any shape
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SavedSettings {
+    /// Linear [`GlobalVolume`]
+    pub volume: f32,
+    pub difficulty: Difficulty,
+    pub boss_flow: BossFlow,
+    pub run_mode: bool,
+}
+
+impl Default for SavedSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            difficulty: Difficulty::default(),
+            boss_flow: BossFlow::default(),
+            run_mode: false,
+        }
+    }
+}
+
+/// Fastest clear of a level. Only clears with the same boss flow and
+/// difficulty are compared.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BestTime {
+    pub level: Level,
+    pub boss_flow: BossFlow,
+    pub difficulty: Difficulty,
+    pub secs: f32,
+}
+
+impl BestTime {
+    fn is_for(&self, level: Level, boss_flow: BossFlow, difficulty: Difficulty) -> bool {
+        self.level == level && self.boss_flow == boss_flow && self.difficulty == difficulty
+    }
+}
+
+/// Options earned by playing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+pub enum Unlock {
+    /// Carrying hearts and ammo over between levels, after the first win
+    RunMode,
+}
+
+impl SaveData {
+    /// The saved data, or a fresh one if the file is missing, unreadable or
+    /// from another version
+    pub fn load() -> Self {
+        let Some(header) = read_config::<SaveHeader>(SAVE_FILE) else {
+            return Self::default();
+        };
+        if header.version != SAVE_VERSION {
+            warn!(
+                "Ignoring {SAVE_FILE} of version {}, expected {SAVE_VERSION}",
+                header.version
+            );
+            return Self::default();
+        }
+        read_config::<SaveData>(SAVE_FILE).unwrap_or_default()
+    }
+
+    /// Whether "Continue" would start anywhere but the beginning
+    pub fn has_progress(&self) -> bool {
+        self.highest_level != Level::default()
+    }
+
+    /// Any level up to the furthest one reached can be played again
+    pub fn is_level_unlocked(&self, level: Level) -> bool {
+        level <= self.highest_level
+    }
+
+    pub fn is_unlocked(&self, unlock: Unlock) -> bool {
+        self.unlocks.contains(&unlock)
+    }
+
+    pub fn best_time(
+        &self,
+        level: Level,
+        boss_flow: BossFlow,
+        difficulty: Difficulty,
+    ) -> Option<f32> {
+        self.best_times
+            .iter()
+            .find(|best| best.is_for(level, boss_flow, difficulty))
+            .map(|best| best.secs)
+    }
+}
+
+fn apply_saved_settings(
+    save: Res<SaveData>,
+    mut volume: ResMut<GlobalVolume>,
+    mut difficulty: ResMut<Difficulty>,
+    mut flow: ResMut<BossFlow>,
+    mut mode: ResMut<RunMode>,
+) {
+    let settings = save.settings;
+    volume.volume = Volume::Linear(settings.volume);
+    *difficulty = settings.difficulty;
+    *flow = settings.boss_flow;
+    mode.0 = settings.run_mode && save.is_unlocked(Unlock::RunMode);
+}
+
+fn record_settings(
+    mut save: ResMut<SaveData>,
+    volume: Res<GlobalVolume>,
+    difficulty: Res<Difficulty>,
+    flow: Res<BossFlow>,
+    mode: Res<RunMode>,
+) {
+    let settings = SavedSettings {
+        volume: volume.volume.to_linear(),
+        difficulty: *difficulty,
+        boss_flow: *flow,
+        run_mode: mode.0,
+    };
+    if save.settings != settings {
+        save.settings = settings;
+    }
+}
+
+/// The boss is down: the time, the next level and maybe the win go in the save
+fn record_level_clear(
+    _: On<StartLoadNext>,
+    mut save: ResMut<SaveData>,
+    run: Res<RunState>,
+    level: Res<State<Level>>,
+    flow: Res<BossFlow>,
+    difficulty: Res<Difficulty>,
+) {
+    let level = *level.get();
+    let secs = run.level_secs();
+    let new = BestTime {
+        level,
+        boss_flow: *flow,
+        difficulty: *difficulty,
+        secs,
+    };
+    match save
+        .best_times
+        .iter_mut()
+        .find(|best| best.is_for(level, *flow, *difficulty))
+    {
+        Some(best) if secs < best.secs => *best = new,
+        Some(_) => (),
+        None => save.best_times.push(new),
+    }
+    if level.is_last() || level == flow.last_level() {
+        if !save.is_unlocked(Unlock::RunMode) {
+            save.unlocks.push(Unlock::RunMode);
+        }
+    } else {
+        save.highest_level = save.highest_level.max(level.next());
+    }
+}
+
+fn write_save(save: Res<SaveData>) {
+    write_config(SAVE_FILE, &*save);
+}
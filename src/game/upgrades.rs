@@ -0,0 +1,75 @@
+//! Upgrades picked between levels. Each one is kept in the
+//! [`RunState`](crate::game::run::RunState) and applied again to every new
+//! player of the run when it spawns.
+
+use bevy::prelude::*;
+use rand::seq::IndexedRandom;
+
+use crate::game::{movement::MovementController, player::Player, rng::GameRng};
+
+/// How many upgrades the screen between levels offers
+pub const UPGRADE_CHOICES: usize = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Upgrade {
+    MaxAmmo,
+    FasterCool,
+    Bounces,
+    MoveSpeed,
+    ExtraHeart,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 5] = [
+        Upgrade::MaxAmmo,
+        Upgrade::FasterCool,
+        Upgrade::Bounces,
+        Upgrade::MoveSpeed,
+        Upgrade::ExtraHeart,
+    ];
+
+    /// [`Player::cool`] is multiplied by this per pick
+    pub const COOL_FACTOR: f32 = 0.8;
+    /// [`MovementController::max_speed`] is multiplied by this per pick
+    pub const SPEED_FACTOR: f32 = 1.15;
+    /// Extra wall bounces of every thrown projectile per pick
+    pub const BOUNCES: usize = 1;
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Upgrade::MaxAmmo => "Quiver",
+            Upgrade::FasterCool => "Quick Hands",
+            Upgrade::Bounces => "Ricochet",
+            Upgrade::MoveSpeed => "Swift Feet",
+            Upgrade::ExtraHeart => "Vitality",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Upgrade::MaxAmmo => "+1 max ammo",
+            Upgrade::FasterCool => "20% faster throws",
+            Upgrade::Bounces => "+1 bounce on every throw",
+            Upgrade::MoveSpeed => "15% faster movement",
+            Upgrade::ExtraHeart => "+1 heart",
+        }
+    }
+
+    /// A random pick of distinct upgrades for the screen between levels
+    pub fn roll_choices(rng: &mut GameRng) -> Vec<Upgrade> {
+        Self::ALL
+            .choose_multiple(rng.gameplay(), UPGRADE_CHOICES)
+            .copied()
+            .collect()
+    }
+
+    pub fn apply(&self, player: &mut Player, controller: &mut MovementController) {
+        match self {
+            Upgrade::MaxAmmo => player.raise_max_ammo(1),
+            Upgrade::FasterCool => player.cool *= Self::COOL_FACTOR,
+            Upgrade::Bounces => player.extra_bounces += Self::BOUNCES,
+            Upgrade::MoveSpeed => controller.max_speed *= Self::SPEED_FACTOR,
+            Upgrade::ExtraHeart => player.raise_max_life(1),
+        }
+    }
+}
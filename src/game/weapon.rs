@@ -0,0 +1,231 @@
+//! What the player throws. A [`Weapon`] bundles the projectile to spawn with
+//! its ammo cost, cool time and how it comes back.
+//! The player carries several in an [`Arsenal`] and switches between them with
+//! the weapon actions (`Q`/`E` and the number keys by default) or the mouse wheel.
+
+use avian2d::prelude::*;
+use bevy::{input::mouse::AccumulatedMouseScroll, prelude::*};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        animation::AnimationAssets,
+        level::{bosses::BossIntroPlaying, projectile_fx::despawn_with_impact, projectiles::*},
+        player::{PLAYER_COLLIDER_CAPSULE, Player},
+    },
+    input::{Action, ActionInput},
+    screens::Screen,
+    ui::dialogue::DialogueQueue,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        record_weapon_switch_input
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(
+                in_state(Screen::Gameplay)
+                    .and(not(resource_exists::<DialogueQueue>))
+                    .and(not(any_with_component::<BossIntroPlaying>)),
+            ),
+    );
+    app.add_systems(FixedUpdate, apply_recall_rules.in_set(PausableSystems));
+}
+
+/// Which projectile bundle a [`Weapon`] spawns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum WeaponKind {
+    Chakram,
+    Basic,
+    BounceDown,
+    Lifespan,
+}
+
+/// How a thrown projectile gets back to the player
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum Recall {
+    /// Comes back when the player clicks with not enough ammo (the chakram way)
+    Manual,
+    /// Comes back by itself as soon as it stops
+    Auto,
+    /// Never comes back. The ammo is returned when it stops.
+    Never,
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub struct Weapon {
+    pub name: &'static str,
+    pub kind: WeaponKind,
+    /// Ammo taken per throw and given back when the projectile is gone
+    pub ammo_cost: usize,
+    /// Extra cool time in seconds on top of [`Player::cool`]
+    pub cooldown: f32,
+    pub recall: Recall,
+}
+
+impl Weapon {
+    pub fn chakram() -> Self {
+        Self {
+            name: "Chakram",
+            kind: WeaponKind::Chakram,
+            ammo_cost: 1,
+            cooldown: 0.0,
+            recall: Recall::Manual,
+        }
+    }
+
+    pub fn disc() -> Self {
+        Self {
+            name: "Disc",
+            kind: WeaponKind::Basic,
+            ammo_cost: 1,
+            cooldown: 0.2,
+            recall: Recall::Manual,
+        }
+    }
+
+    pub fn ricochet() -> Self {
+        Self {
+            name: "Ricochet",
+            kind: WeaponKind::BounceDown,
+            ammo_cost: 2,
+            cooldown: 0.4,
+            recall: Recall::Auto,
+        }
+    }
+
+    pub fn dart() -> Self {
+        Self {
+            name: "Dart",
+            kind: WeaponKind::Lifespan,
+            ammo_cost: 1,
+            cooldown: 0.1,
+            recall: Recall::Never,
+        }
+    }
+
+    /// Spawns the projectile of this weapon thrown from `xy`
+    pub fn spawn_projectile(
+        &self,
+        commands: &mut Commands,
+        xy: Vec2,
+        direction: Dir2,
+        anim_assets: &AnimationAssets,
+    ) -> Entity {
+        let (radius, height) = PLAYER_COLLIDER_CAPSULE;
+        let mut projectile = match self.kind {
+            WeaponKind::Chakram => commands.spawn(player_chakra::<Friendly>(
+                xy,
+                direction,
+                radius,
+                height,
+                anim_assets,
+            )),
+            WeaponKind::Basic => commands.spawn(basic_projectile::<Friendly>(
+                xy,
+                direction,
+                radius + height,
+                anim_assets,
+            )),
+            WeaponKind::BounceDown => commands.spawn(bounce_down_projectile::<Friendly>(
+                xy,
+                direction,
+                radius + height,
+                anim_assets,
+            )),
+            WeaponKind::Lifespan => commands.spawn(lifespan_projectile::<Friendly>(
+                xy,
+                direction,
+                radius + height,
+                anim_assets,
+            )),
+        };
+        projectile.insert((self.recall, AmmoCost(self.ammo_cost)));
+        projectile.id()
+    }
+}
+
+/// The weapons the player carries and which one is in hand
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Arsenal {
+    pub weapons: Vec<Weapon>,
+    pub current: usize,
+}
+
+impl Default for Arsenal {
+    fn default() -> Self {
+        Self {
+            weapons: vec![
+                Weapon::chakram(),
+                Weapon::disc(),
+                Weapon::ricochet(),
+                Weapon::dart(),
+            ],
+            current: 0,
+        }
+    }
+}
+
+impl Arsenal {
+    pub fn current(&self) -> &Weapon {
+        &self.weapons[self.current % self.weapons.len()]
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.weapons.len() {
+            self.current = index;
+        }
+    }
+
+    pub fn cycle(&mut self, step: isize) {
+        let n = self.weapons.len() as isize;
+        self.current = (self.current as isize + step).rem_euclid(n) as usize;
+    }
+}
+
+fn record_weapon_switch_input(
+    input: ActionInput,
+    scroll: Res<AccumulatedMouseScroll>,
+    mut arsenal: Single<&mut Arsenal, With<Player>>,
+) {
+    for (i, slot) in Action::WEAPON_SLOTS.iter().enumerate() {
+        if input.just_pressed(*slot) {
+            arsenal.select(i);
+        }
+    }
+    if input.just_pressed(Action::PreviousWeapon) {
+        arsenal.cycle(-1);
+    }
+    if input.just_pressed(Action::NextWeapon) {
+        arsenal.cycle(1);
+    }
+    // Wheel up picks the previous weapon like most shooters
+    if scroll.delta.y > 0.0 {
+        arsenal.cycle(-1);
+    } else if scroll.delta.y < 0.0 {
+        arsenal.cycle(1);
+    }
+}
+
+/// Stopped projectiles are taken care of by their [`Recall`] rule
+fn apply_recall_rules(
+    mut commands: Commands,
+    query: Query<(Entity, &LinearVelocity, &Recall), (With<Friendly>, Without<Recalled>)>,
+) {
+    for (entity, velocity, recall) in &query {
+        if velocity.0 != Vec2::ZERO {
+            continue;
+        }
+        match recall {
+            Recall::Manual => {}
+            Recall::Auto => {
+                commands.entity(entity).insert(Recalled);
+            }
+            Recall::Never => {
+                despawn_with_impact(&mut commands, entity);
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! Player input as actions instead of raw keys.
+//! Each [`Action`] has its bindings in the [`InputMap`] resource, which the
+//! controls menu rebinds and saves to the config directory.
+//! Gameplay reads [`ActionInput`], or the `action_*` run conditions.
+//!
+//! Every connected gamepad drives the same actions, plus the sticks for moving
+//! and aiming. Gamepads are entities, so plugging one in mid-game just works.
+//! The [`touch`] controls act like one more gamepad.
+
+mod touch;
+
+use std::collections::BTreeMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::config::{read_config, write_config};
+
+pub use touch::{JOYSTICK_RADIUS, TouchControls};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(touch::plugin);
+    app.insert_resource(InputMap::load());
+    app.add_systems(
+        Update,
+        save_input_map.run_if(resource_changed::<InputMap>.and(not(resource_added::<InputMap>))),
+    );
+    app.add_systems(Update, log_gamepad_connections);
+}
+
+const INPUT_MAP_FILE: &str = "bindings.ron";
+
+/// Stick tilt below this is ignored
+pub const STICK_DEADZONE: f32 = 0.2;
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Reflect,
+)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    /// Hold to charge, release to throw
+    Throw,
+    /// Call back the thrown weapons that wait for it
+    Recall,
+    Dash,
+    PreviousWeapon,
+    NextWeapon,
+    Weapon1,
+    Weapon2,
+    Weapon3,
+    Weapon4,
+    Pause,
+    /// Advance the dialogue
+    Confirm,
+}
+
+impl Action {
+    /// In the order the controls menu lists them
+    pub const ALL: [Action; 15] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Throw,
+        Action::Recall,
+        Action::Dash,
+        Action::PreviousWeapon,
+        Action::NextWeapon,
+        Action::Weapon1,
+        Action::Weapon2,
+        Action::Weapon3,
+        Action::Weapon4,
+        Action::Pause,
+        Action::Confirm,
+    ];
+
+    pub const WEAPON_SLOTS: [Action; 4] = [
+        Action::Weapon1,
+        Action::Weapon2,
+        Action::Weapon3,
+        Action::Weapon4,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move Up",
+            Action::MoveDown => "Move Down",
+            Action::MoveLeft => "Move Left",
+            Action::MoveRight => "Move Right",
+            Action::Throw => "Throw",
+            Action::Recall => "Recall",
+            Action::Dash => "Dash",
+            Action::PreviousWeapon => "Previous Weapon",
+            Action::NextWeapon => "Next Weapon",
+            Action::Weapon1 => "Weapon 1",
+            Action::Weapon2 => "Weapon 2",
+            Action::Weapon3 => "Weapon 3",
+            Action::Weapon4 => "Weapon 4",
+            Action::Pause => "Pause",
+            Action::Confirm => "Confirm",
+        }
+    }
+
+    /// Whether both can be on the same button. Confirm only advances the
+    /// dialogue, which stops gameplay but not the pause.
+    pub fn can_share_binding(self, other: Action) -> bool {
+        match (self, other) {
+            (Action::Confirm, other) | (other, Action::Confirm) => other != Action::Pause,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+}
+
+impl Binding {
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                // KeyW -> W, Digit1 -> 1, ArrowUp stays
+                name.strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name)
+                    .to_string()
+            }
+            Binding::Mouse(button) => format!("Mouse {button:?}"),
+            Binding::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, Binding::Gamepad(_))
+    }
+}
+
+/// Every action with the keys and buttons that trigger it
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InputMap {
+    pub bindings: BTreeMap<Action, Vec<Binding>>,
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use Binding::*;
+        let bindings = [
+            (
+                Action::MoveUp,
+                vec![Key(KeyCode::KeyW), Gamepad(GamepadButton::DPadUp)],
+            ),
+            (
+                Action::MoveDown,
+                vec![Key(KeyCode::KeyS), Gamepad(GamepadButton::DPadDown)],
+            ),
+            (
+                Action::MoveLeft,
+                vec![Key(KeyCode::KeyA), Gamepad(GamepadButton::DPadLeft)],
+            ),
+            (
+                Action::MoveRight,
+                vec![Key(KeyCode::KeyD), Gamepad(GamepadButton::DPadRight)],
+            ),
+            (
+                Action::Throw,
+                vec![
+                    Mouse(MouseButton::Left),
+                    Gamepad(GamepadButton::RightTrigger2),
+                ],
+            ),
+            (
+                Action::Recall,
+                vec![
+                    Mouse(MouseButton::Right),
+                    Gamepad(GamepadButton::LeftTrigger2),
+                ],
+            ),
+            (
+                Action::Dash,
+                vec![Key(KeyCode::ShiftLeft), Gamepad(GamepadButton::East)],
+            ),
+            (
+                Action::PreviousWeapon,
+                vec![Key(KeyCode::KeyQ), Gamepad(GamepadButton::LeftTrigger)],
+            ),
+            (
+                Action::NextWeapon,
+                vec![Key(KeyCode::KeyE), Gamepad(GamepadButton::RightTrigger)],
+            ),
+            (Action::Weapon1, vec![Key(KeyCode::Digit1)]),
+            (Action::Weapon2, vec![Key(KeyCode::Digit2)]),
+            (Action::Weapon3, vec![Key(KeyCode::Digit3)]),
+            (Action::Weapon4, vec![Key(KeyCode::Digit4)]),
+            (
+                Action::Pause,
+                vec![
+                    Key(KeyCode::KeyP),
+                    Key(KeyCode::Escape),
+                    Gamepad(GamepadButton::Start),
+                ],
+            ),
+            (
+                Action::Confirm,
+                vec![
+                    Key(KeyCode::Space),
+                    Key(KeyCode::Enter),
+                    Mouse(MouseButton::Left),
+                    Gamepad(GamepadButton::South),
+                ],
+            ),
+        ];
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl InputMap {
+    /// The saved bindings, with defaults for any action the file lacks
+    pub fn load() -> Self {
+        let mut map = Self::default();
+        if let Some(saved) = read_config::<InputMap>(INPUT_MAP_FILE) {
+            map.bindings.extend(saved.bindings);
+        }
+        map
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the bindings of `action` on the same kind of device, so
+    /// rebinding a key keeps the gamepad button and the other way around.
+    /// An action that already had `binding` gets the replaced one instead,
+    /// unless the two may share it.
+    pub fn rebind(&mut self, action: Action, binding: Binding) {
+        let bindings = self.bindings.entry(action).or_default();
+        let replaced = bindings
+            .iter()
+            .copied()
+            .find(|b| b.is_gamepad() == binding.is_gamepad() && *b != binding);
+        bindings.retain(|b| b.is_gamepad() != binding.is_gamepad());
+        bindings.push(binding);
+        for (other, other_bindings) in &mut self.bindings {
+            if *other == action || action.can_share_binding(*other) {
+                continue;
+            }
+            let Some(index) = other_bindings.iter().position(|b| *b == binding) else {
+                continue;
+            };
+            other_bindings.remove(index);
+            if let Some(replaced) = replaced
+                && !other_bindings.contains(&replaced)
+            {
+                other_bindings.push(replaced);
+            }
+        }
+    }
+}
+
+fn save_input_map(map: Res<InputMap>) {
+    write_config(INPUT_MAP_FILE, &*map);
+}
+
+/// Reads actions through the [`InputMap`]
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    map: Res<'w, InputMap>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+    touch: Res<'w, TouchControls>,
+}
+
+impl ActionInput<'_, '_> {
+    fn any(
+        &self,
+        action: Action,
+        key: impl Fn(&ButtonInput<KeyCode>, KeyCode) -> bool,
+        mouse: impl Fn(&ButtonInput<MouseButton>, MouseButton) -> bool,
+        pad: impl Fn(&Gamepad, GamepadButton) -> bool,
+        touch: impl Fn(&TouchControls) -> bool,
+    ) -> bool {
+        // The touch controls only throw, they have no bindings
+        if action == Action::Throw && touch(&self.touch) {
+            return true;
+        }
+        self.map.get(action).iter().any(|binding| match binding {
+            Binding::Key(k) => key(&self.keys, *k),
+            Binding::Mouse(b) => mouse(&self.mouse, *b),
+            Binding::Gamepad(b) => self.gamepads.iter().any(|g| pad(g, *b)),
+        })
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.any(
+            action,
+            |i, k| i.pressed(k),
+            |i, b| i.pressed(b),
+            |g, b| g.pressed(b),
+            TouchControls::throw_pressed,
+        )
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.any(
+            action,
+            |i, k| i.just_pressed(k),
+            |i, b| i.just_pressed(b),
+            |g, b| g.just_pressed(b),
+            TouchControls::throw_just_pressed,
+        )
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        self.any(
+            action,
+            |i, k| i.just_released(k),
+            |i, b| i.just_released(b),
+            |g, b| g.just_released(b),
+            TouchControls::throw_just_released,
+        )
+    }
+
+    /// Where to move, with the analog tilt of the left stick or the touch
+    /// joystick kept.
+    /// The move actions give full speed, diagonals included.
+    pub fn movement(&self) -> Vec2 {
+        let mut intent = Vec2::ZERO;
+        if self.pressed(Action::MoveUp) {
+            intent.y += 1.0;
+        }
+        if self.pressed(Action::MoveDown) {
+            intent.y -= 1.0;
+        }
+        if self.pressed(Action::MoveLeft) {
+            intent.x -= 1.0;
+        }
+        if self.pressed(Action::MoveRight) {
+            intent.x += 1.0;
+        }
+        if intent != Vec2::ZERO {
+            return intent.normalize();
+        }
+        self.stick(Gamepad::left_stick)
+            .map(|stick| stick.clamp_length_max(1.0))
+            .unwrap_or_else(|| self.touch.movement())
+    }
+
+    /// Right stick direction while it is tilted, or the touch aim drag
+    pub fn aim(&self) -> Option<Dir2> {
+        self.stick(Gamepad::right_stick)
+            .and_then(|stick| Dir2::new(stick).ok())
+            .or_else(|| self.touch.aim())
+    }
+
+    pub fn touch(&self) -> &TouchControls {
+        &self.touch
+    }
+
+    /// The first gamepad with this stick out of the deadzone
+    fn stick(&self, stick: impl Fn(&Gamepad) -> Vec2) -> Option<Vec2> {
+        self.gamepads
+            .iter()
+            .map(stick)
+            .find(|v| v.length() > STICK_DEADZONE)
+    }
+}
+
+/// Run condition like [`input_just_pressed`](bevy::input::common_conditions::input_just_pressed)
+pub fn action_just_pressed(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_pressed(action)
+}
+
+pub fn action_just_released(action: Action) -> impl FnMut(ActionInput) -> bool + Clone {
+    move |input: ActionInput| input.just_released(action)
+}
+
+/// Escape, or East on a gamepad
+pub fn back_just_pressed(keys: Res<ButtonInput<KeyCode>>, gamepads: Query<&Gamepad>) -> bool {
+    keys.just_pressed(KeyCode::Escape)
+        || gamepads.iter().any(|g| g.just_pressed(GamepadButton::East))
+}
+
+fn log_gamepad_connections(mut connections: MessageReader<GamepadConnectionEvent>) {
+    for event in connections.read() {
+        match &event.connection {
+            GamepadConnection::Connected { name, .. } => info!("Gamepad connected: {name}"),
+            GamepadConnection::Disconnected => info!("Gamepad disconnected"),
+        }
+    }
+}
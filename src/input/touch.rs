@@ -0,0 +1,161 @@
+//! Touch screen controls. A touch on the left half of the screen is a floating
+//! joystick for moving, one on the right half charges a throw: drag to aim,
+//! lift to throw, or just tap where to throw.
+//!
+//! [`ActionInput`](super::ActionInput) reads these like a gamepad, so they
+//! share the throw and movement paths with the other controls.
+
+use bevy::{
+    input::{InputSystems, mouse::AccumulatedMouseMotion},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TouchControls>();
+    app.add_systems(PreUpdate, update_touch_controls.after(InputSystems));
+}
+
+/// Joystick distance for full speed, in logical pixels
+pub const JOYSTICK_RADIUS: f32 = 60.0;
+/// Joystick tilt below this is ignored
+const JOYSTICK_DEADZONE: f32 = 0.15;
+/// A throw touch that moved less than this is a tap
+const AIM_DRAG_MIN: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TouchStick {
+    id: u64,
+    /// Where the touch started
+    pub origin: Vec2,
+    pub position: Vec2,
+}
+
+impl TouchStick {
+    fn new(touch: &Touch) -> Self {
+        Self {
+            id: touch.id(),
+            origin: touch.position(),
+            position: touch.position(),
+        }
+    }
+
+    /// The drag in world orientation, with y up
+    pub fn offset(&self) -> Vec2 {
+        let offset = self.position - self.origin;
+        Vec2::new(offset.x, -offset.y)
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct TouchControls {
+    /// Set by any touch, cleared by the keyboard or mouse
+    pub active: bool,
+    pub joystick: Option<TouchStick>,
+    pub throw: Option<TouchStick>,
+    throw_just_pressed: bool,
+    throw_just_released: bool,
+    /// Screen position of a tap lifted this frame
+    tap: Option<Vec2>,
+}
+
+impl TouchControls {
+    pub fn movement(&self) -> Vec2 {
+        let Some(joystick) = self.joystick else {
+            return Vec2::ZERO;
+        };
+        let tilt = (joystick.offset() / JOYSTICK_RADIUS).clamp_length_max(1.0);
+        if tilt.length() < JOYSTICK_DEADZONE {
+            Vec2::ZERO
+        } else {
+            tilt
+        }
+    }
+
+    /// The drag direction of the throw touch, once it is more than a tap
+    pub fn aim(&self) -> Option<Dir2> {
+        self.throw
+            .map(|throw| throw.offset())
+            .filter(|offset| offset.length() >= AIM_DRAG_MIN)
+            .and_then(|offset| Dir2::new(offset).ok())
+    }
+
+    pub fn throw_pressed(&self) -> bool {
+        self.throw.is_some()
+    }
+
+    pub fn throw_just_pressed(&self) -> bool {
+        self.throw_just_pressed
+    }
+
+    pub fn throw_just_released(&self) -> bool {
+        self.throw_just_released
+    }
+
+    /// Where a throw was tapped this frame, to throw toward like the cursor
+    pub fn tap(&self) -> Option<Vec2> {
+        self.tap
+    }
+}
+
+fn update_touch_controls(
+    touches: Res<Touches>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut controls: ResMut<TouchControls>,
+) {
+    let controls = &mut *controls;
+    controls.throw_just_pressed = false;
+    controls.throw_just_released = false;
+    controls.tap = None;
+
+    for touch in touches.iter_just_pressed() {
+        controls.active = true;
+        if touch.position().x < window.width() / 2.0 {
+            if controls.joystick.is_none() {
+                controls.joystick = Some(TouchStick::new(touch));
+            }
+        } else if controls.throw.is_none() {
+            controls.throw = Some(TouchStick::new(touch));
+            controls.throw_just_pressed = true;
+        }
+    }
+
+    for stick in [&mut controls.joystick, &mut controls.throw]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(touch) = touches.get_pressed(stick.id) {
+            stick.position = touch.position();
+        }
+    }
+
+    for touch in touches
+        .iter_just_released()
+        .chain(touches.iter_just_canceled())
+    {
+        if controls.joystick.is_some_and(|j| j.id == touch.id()) {
+            controls.joystick = None;
+        }
+        if let Some(mut throw) = controls.throw.filter(|t| t.id == touch.id()) {
+            throw.position = touch.position();
+            if throw.offset().length() < AIM_DRAG_MIN {
+                controls.tap = Some(touch.position());
+            }
+            controls.throw = None;
+            controls.throw_just_released = true;
+        }
+    }
+
+    // Some browsers also report touches as mouse moves, so those only count
+    // while no finger is down
+    let mouse_moved = mouse_motion.delta != Vec2::ZERO && touches.iter().next().is_none();
+    if keys.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || mouse_moved
+    {
+        controls.active = false;
+    }
+}
@@ -8,6 +8,7 @@ mod audio;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
+mod input;
 mod screens;
 mod ui;
 mod utils;
@@ -86,6 +87,7 @@ impl Plugin for AppPlugin {
             asset_tracking::plugin,
             audio::plugin,
             game::plugin,
+            input::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             screens::plugin,
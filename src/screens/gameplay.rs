@@ -1,16 +1,22 @@
 //! The screen state for the main gameplay.
 
 use avian2d::prelude::{Physics, PhysicsTime};
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 use bevy_aseprite_ultra::prelude::{AseAnimation, ManualTick};
 
 use crate::{
     AppSystems, Pause,
     audio::sound_effect,
     game::{
-        level::{Level, bosses::Boss, enemies::Enemy, sfx_intro, spawn_level},
+        level::{
+            Level,
+            bosses::{Boss, BossFlow},
+            enemies::Enemy,
+            sfx_intro, spawn_level,
+        },
         player::*,
     },
+    input::{Action, action_just_pressed, back_just_pressed},
     screens::Screen,
     ui::{menus::Menu, theme::interaction::InteractionAssets},
     utils::tiled::spawn_tiled_map,
@@ -35,12 +41,15 @@ pub(super) fn plugin(app: &mut App) {
             (pause, spawn_pause_overlay, open_pause_menu).run_if(
                 in_state(Screen::Gameplay)
                     .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
+                    .and(action_just_pressed(Action::Pause)),
             ),
+            // Escape and East are the menus' own back keys
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
-                    .and(input_just_pressed(KeyCode::KeyP)),
+                    .and(not(in_state(Menu::Upgrade)))
+                    .and(action_just_pressed(Action::Pause))
+                    .and(not(back_just_pressed)),
             ),
             despawn_finished_transitions.run_if(
                 in_state(Screen::Gameplay)
@@ -238,17 +247,19 @@ fn on_boss_defeated(_: On<BossDefeated>, mut cmd: Commands, mut time: ResMut<Tim
     ));
 }
 
+/// Between levels the upgrade menu picks a blessing, which then loads the next level
 fn transition_level(
     _: On<StartLoadNext>,
     current_level: Res<State<Level>>,
-    mut next_level: ResMut<NextState<Level>>,
-    mut next_screen: ResMut<NextState<Screen>>,
+    flow: Res<BossFlow>,
+    mut next_pause: ResMut<NextState<Pause>>,
     mut next_menu: ResMut<NextState<Menu>>,
 ) {
     let lev = current_level.get();
-    if !lev.is_last() {
-        next_level.set(lev.next());
-        next_screen.set(Screen::Loading);
+    if !lev.is_last() && *lev != flow.last_level() {
+        // Clicking a choice must not throw
+        next_pause.set(Pause(true));
+        next_menu.set(Menu::Upgrade);
     } else {
         next_menu.set(Menu::Win);
     }
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 
 use crate::{
     game::level::{Level, LevelAssets, bosses::BossIntroPlaying},
+    input::{Action, ActionInput},
     ui::theme::palette::{BACKGROUND_DARK, BUTTON_BORDER, BUTTON_TEXT},
 };
 
@@ -77,18 +78,14 @@ pub fn spawn_dialogue_ui(cmd: &mut Commands, assets: &LevelAssets, initial_text:
 fn advance_dialogue(
     mut cmd: Commands,
     mut dialogue: ResMut<DialogueQueue>,
-    keys: Res<ButtonInput<KeyCode>>,
-    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    input: ActionInput,
     touches: Res<Touches>,
     mut text_query: Query<&mut Text, With<DialogueUiText>>,
     ui_query: Query<Entity, With<DialogueUi>>,
     intro_q: Query<Entity, With<BossIntroPlaying>>,
     mut time: ResMut<Time<Physics>>,
 ) {
-    let explicit_continue = keys.just_pressed(KeyCode::Space)
-        || keys.just_pressed(KeyCode::Enter)
-        || mouse_buttons.just_pressed(MouseButton::Left)
-        || touches.any_just_pressed();
+    let explicit_continue = input.just_pressed(Action::Confirm) || touches.any_just_pressed();
     if explicit_continue {
         let current_line = dialogue.lines[dialogue.current_index].clone();
         if !dialogue.is_finished {
@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 
 use crate::{
-    game::level::{Level, bosses::BossPhase, enemies::Enemy},
+    game::level::{
+        Level,
+        boss_phases::PhaseProgress,
+        bosses::{Boss, BossPhase},
+        enemies::Enemy,
+    },
     screens::Screen,
     ui::menus::Menu,
 };
@@ -15,9 +20,16 @@ pub(super) fn plugin(app: &mut App) {
             spawn_healthbar.run_if(in_state(Level::Phase3)),
         ),
     )
-    .add_systems(Update, update_health_bar.run_if(in_state(Screen::Gameplay)));
+    .add_systems(
+        Update,
+        (spawn_phase_markers, update_health_bar).run_if(in_state(Screen::Gameplay)),
+    );
 }
 
+/// Marks the frame the bars sit in
+#[derive(Component)]
+struct HealthBarFrame;
+
 /// Marks the health bar fill node
 #[derive(Component)]
 struct HealthBarFill;
@@ -43,6 +55,7 @@ fn spawn_healthbar(mut cmd: Commands) {
         DespawnOnEnter(Screen::Loading),
         DespawnOnEnter(Menu::Win), // for when credits menu is opened at the end of gameplay
         children![(
+            HealthBarFrame,
             Node {
                 width: Val::Px(400.0),
                 height: Val::Px(20.0),
@@ -91,13 +104,38 @@ fn spawn_healthbar(mut cmd: Commands) {
     ));
 }
 
+/// A tick on the bar where each next phase of a single-fight boss starts
+fn spawn_phase_markers(
+    mut cmd: Commands,
+    boss_q: Query<&PhaseProgress, (With<Boss>, Added<PhaseProgress>)>,
+    frame: Option<Single<Entity, With<HealthBarFrame>>>,
+) {
+    let (Ok(progress), Some(frame)) = (boss_q.single(), frame) else {
+        return;
+    };
+    for &at_life in &progress.thresholds {
+        cmd.spawn((
+            Name::new("Phase Marker"),
+            Node {
+                width: Val::Px(2.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                left: Val::Percent(at_life as f32 / progress.max_life as f32 * 100.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(1.0, 0.753, 0.0)),
+            ChildOf(*frame),
+        ));
+    }
+}
+
 fn update_health_bar(
     time: Res<Time>,
-    boss_q: Query<(&BossPhase, &Enemy)>,
+    boss_q: Query<(&Enemy, Option<&BossPhase>, Option<&PhaseProgress>), With<Boss>>,
     mut fill_query: Query<&mut Node, (With<HealthBarFill>, Without<GhostBarFill>)>,
     mut ghost_query: Query<&mut Node, (With<GhostBarFill>, Without<HealthBarFill>)>,
 ) {
-    let Ok((boss, enemy)) = boss_q.single() else {
+    let Ok((enemy, boss, progress)) = boss_q.single() else {
         return;
     };
     let Ok(mut fill_node) = fill_query.single_mut() else {
@@ -107,8 +145,13 @@ fn update_health_bar(
         return;
     };
 
-    let target_pct =
-        (boss.current_base_hp() + enemy.life as u32) as f32 / boss.total_hp() as f32 * 100.0;
+    let target_pct = match (boss, progress) {
+        (Some(boss), _) => {
+            (boss.current_base_hp() + enemy.life as u32) as f32 / boss.total_hp() as f32 * 100.0
+        }
+        (None, Some(progress)) => enemy.life as f32 / progress.max_life as f32 * 100.0,
+        (None, None) => return,
+    };
 
     // Snap the main bar
     fill_node.width = Val::Percent(target_pct);
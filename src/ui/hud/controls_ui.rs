@@ -2,12 +2,17 @@ use bevy::prelude::*;
 
 use crate::{
     game::level::LevelAssets,
+    input::TouchControls,
     screens::Screen,
     ui::{menus::Menu, theme::widget::tutorial_label},
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_tutorial_ui);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_tutorial_ui)
+        .add_systems(
+            Update,
+            hide_tutorial_ui_on_touch.run_if(in_state(Screen::Gameplay)),
+        );
 }
 
 /// Marker component for tutorial UI
@@ -196,3 +201,17 @@ fn spawn_tutorial_ui(
         ],
     ));
 }
+
+/// The keyboard and mouse hints make no sense on a touch screen
+fn hide_tutorial_ui_on_touch(
+    touch: Res<TouchControls>,
+    mut tutorial: Query<&mut Visibility, With<TutorialUi>>,
+) {
+    for mut visibility in &mut tutorial {
+        *visibility = if touch.active {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
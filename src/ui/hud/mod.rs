@@ -3,11 +3,15 @@ use bevy::prelude::*;
 mod boss_healthbar;
 mod controls_ui;
 mod player_stats;
+mod touch_controls;
+mod wave_progress;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         player_stats::plugin,
         boss_healthbar::plugin,
         controls_ui::plugin,
+        touch_controls::plugin,
+        wave_progress::plugin,
     ));
 }
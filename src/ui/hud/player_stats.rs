@@ -7,21 +7,35 @@ use bevy::{
 
 use crate::{
     game::{
-        level::{Level, LevelAssets},
+        dash::Dash,
+        level::{LevelAssets, spawn_level},
         player::Player,
+        weapon::Arsenal,
     },
     screens::Screen,
-    ui::menus::Menu,
+    ui::{
+        menus::Menu,
+        theme::palette::{BACKGROUND_DARK, BUTTON_BORDER, HEADER_TEXT, LABEL_TEXT},
+    },
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_playerstats_ui)
-        .add_systems(
-            Update,
-            update_gameplay_stats
-                .run_if(in_state(Screen::Gameplay))
-                .run_if(in_state(Menu::None)),
-        );
+    // Sized after the player, upgrades included
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_playerstats_ui.after(spawn_level),
+    )
+    .add_systems(
+        Update,
+        (
+            update_gameplay_stats,
+            update_weapon_label,
+            update_coin_label,
+            update_dash_cooldown,
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .run_if(in_state(Menu::None)),
+    );
 }
 
 /// Marker component for the UI container
@@ -40,6 +54,18 @@ struct AmmoIcon {
     index: usize,
 }
 
+/// Marker component for the current weapon label next to the ammo icons
+#[derive(Component)]
+struct WeaponLabel;
+
+/// Marker component for the coin count
+#[derive(Component)]
+struct CoinLabel;
+
+/// Marker component for the bar that fills up as the dash cools down
+#[derive(Component)]
+struct DashCooldownFill;
+
 /// Atlas indices for the UI sprites (4x4 grid of 32x32 pixels)
 #[derive(Clone, Copy)]
 enum UiIconAtlas {
@@ -51,6 +77,8 @@ enum UiIconAtlas {
 
 const ICON_SIZE: f32 = 32.0;
 const ICON_SPACING: f32 = 8.0;
+const DASH_BAR_WIDTH: f32 = 80.0;
+const DASH_BAR_HEIGHT: f32 = 8.0;
 
 /// Spawns the gameplay UI showing player health and ammo and switches mouse cursor to aim
 pub fn spawn_playerstats_ui(
@@ -58,12 +86,13 @@ pub fn spawn_playerstats_ui(
     assets: Res<LevelAssets>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     window: Single<Entity, With<Window>>,
-    current_level: Res<State<Level>>,
+    player: Single<&Player>,
 ) {
     let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 2, 2, None, None);
     let layout_handle = texture_atlas_layouts.add(layout);
 
-    let stats = current_level.player_stats();
+    let hearts = player.max_life;
+    let ammo = player.max_ammo;
 
     cmd.spawn((
         Name::new("Gameplay UI"),
@@ -84,7 +113,7 @@ pub fn spawn_playerstats_ui(
     .with_children(|ui| {
         ui.spawn(stat_container("Hearts Container"))
             .with_children(|ui| {
-                for i in 0..stats {
+                for i in 0..hearts {
                     ui.spawn((
                         HeartIcon { index: i },
                         ImageNode::from_atlas_image(
@@ -104,7 +133,18 @@ pub fn spawn_playerstats_ui(
             });
         ui.spawn(stat_container("Ammo Container"))
             .with_children(|ui| {
-                for i in 0..stats {
+                ui.spawn((
+                    Name::new("Weapon Label"),
+                    WeaponLabel,
+                    Text::new(""),
+                    TextFont {
+                        font: assets.level_font.clone(),
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(LABEL_TEXT),
+                ));
+                for i in 0..ammo {
                     ui.spawn((
                         Name::new(format!("Ammo {}", i)),
                         AmmoIcon { index: i },
@@ -123,6 +163,54 @@ pub fn spawn_playerstats_ui(
                     ));
                 }
             });
+        ui.spawn(stat_container("Coins Container"))
+            .with_children(|ui| {
+                ui.spawn((
+                    Name::new("Coin Label"),
+                    CoinLabel,
+                    Text::new("Coins 0"),
+                    TextFont {
+                        font: assets.level_font.clone(),
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(LABEL_TEXT),
+                ));
+            });
+        ui.spawn(stat_container("Dash Container"))
+            .with_children(|ui| {
+                ui.spawn((
+                    Name::new("Dash Label"),
+                    Text::new("Dash"),
+                    TextFont {
+                        font: assets.level_font.clone(),
+                        font_size: 20.0,
+                        ..default()
+                    },
+                    TextColor(LABEL_TEXT),
+                ));
+                ui.spawn((
+                    Name::new("Dash Cooldown Bar"),
+                    Node {
+                        width: Val::Px(DASH_BAR_WIDTH),
+                        height: Val::Px(DASH_BAR_HEIGHT),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BackgroundColor(BACKGROUND_DARK),
+                    BorderColor::all(BUTTON_BORDER),
+                    children![(
+                        Name::new("Dash Cooldown Fill"),
+                        DashCooldownFill,
+                        Node {
+                            width: percent(100),
+                            height: percent(100),
+                            ..default()
+                        },
+                        BackgroundColor(HEADER_TEXT),
+                    )],
+                ));
+            });
     });
 
     // spawning aim cursor
@@ -177,3 +265,38 @@ fn update_gameplay_stats(
         }
     }
 }
+
+/// Shows the weapon in hand when the player switches
+fn update_weapon_label(
+    arsenal_query: Query<&Arsenal, Changed<Arsenal>>,
+    mut label: Single<&mut Text, With<WeaponLabel>>,
+) {
+    if let Ok(arsenal) = arsenal_query.single() {
+        let weapon = arsenal.current();
+        label.0 = format!("{} x{}", weapon.name, weapon.ammo_cost);
+    }
+}
+
+/// Counts the coins picked up
+fn update_coin_label(
+    player_query: Query<&Player, Changed<Player>>,
+    mut label: Single<&mut Text, With<CoinLabel>>,
+) {
+    if let Ok(player) = player_query.single() {
+        label.0 = format!("Coins {}", player.coins);
+    }
+}
+
+/// Fills the dash bar back up over the cooldown, dimmed until it is ready
+fn update_dash_cooldown(
+    dash: Single<&Dash, With<Player>>,
+    fill: Single<(&mut Node, &mut BackgroundColor), With<DashCooldownFill>>,
+) {
+    let (mut node, mut color) = fill.into_inner();
+    node.width = percent(dash.readiness() * 100.0);
+    color.0 = if dash.is_ready() {
+        HEADER_TEXT
+    } else {
+        LABEL_TEXT
+    };
+}
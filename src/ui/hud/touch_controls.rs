@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::level::LevelAssets,
+    input::{JOYSTICK_RADIUS, TouchControls},
+    screens::Screen,
+    ui::{menus::Menu, theme::widget::tutorial_label},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_touch_controls_ui)
+        .add_systems(
+            Update,
+            update_touch_controls_ui.run_if(in_state(Screen::Gameplay)),
+        );
+}
+
+/// Marker component for the touch controls container, shown while touch is in use
+#[derive(Component)]
+struct TouchControlsUi;
+
+/// Marker component for the ring the joystick moves in
+#[derive(Component)]
+struct JoystickBase;
+
+/// Marker component for the part of the joystick under the finger
+#[derive(Component)]
+struct JoystickKnob;
+
+const KNOB_RADIUS: f32 = JOYSTICK_RADIUS * 0.45;
+/// Where the joystick rests, from the bottom left corner
+const JOYSTICK_MARGIN: f32 = 40.0;
+
+fn spawn_touch_controls_ui(mut cmd: Commands, assets: Res<LevelAssets>) {
+    cmd.spawn((
+        Name::new("Touch Controls UI"),
+        TouchControlsUi,
+        GlobalZIndex(1),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        DespawnOnExit(Screen::Gameplay),
+        DespawnOnEnter(Menu::Win),
+        children![
+            (
+                Name::new("Joystick Base"),
+                JoystickBase,
+                Pickable::IGNORE,
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Px(JOYSTICK_RADIUS * 2.0),
+                    height: Val::Px(JOYSTICK_RADIUS * 2.0),
+                    border: UiRect::all(Val::Px(2.0)),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.1)),
+                BorderColor::all(Color::srgba(1.0, 1.0, 1.0, 0.4)),
+                children![(
+                    Name::new("Joystick Knob"),
+                    JoystickKnob,
+                    Pickable::IGNORE,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Px(KNOB_RADIUS * 2.0),
+                        height: Val::Px(KNOB_RADIUS * 2.0),
+                        border_radius: BorderRadius::MAX,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.4)),
+                )],
+            ),
+            (
+                Name::new("Throw Zone Hint"),
+                Pickable::IGNORE,
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(30.0),
+                    bottom: Val::Px(30.0),
+                    ..default()
+                },
+                children![tutorial_label(
+                    "Right side: drag to aim, lift to throw",
+                    assets.level_font.clone()
+                )],
+            ),
+        ],
+    ));
+}
+
+fn update_touch_controls_ui(
+    touch: Res<TouchControls>,
+    window: Single<&Window>,
+    mut container: Single<&mut Visibility, With<TouchControlsUi>>,
+    mut base: Single<&mut Node, (With<JoystickBase>, Without<JoystickKnob>)>,
+    mut knob: Single<&mut Node, (With<JoystickKnob>, Without<JoystickBase>)>,
+) {
+    **container = if touch.active {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if !touch.active {
+        return;
+    }
+
+    // The joystick follows where the finger lands and rests in the corner otherwise
+    let resting = Vec2::new(
+        JOYSTICK_MARGIN + JOYSTICK_RADIUS,
+        window.height() - JOYSTICK_MARGIN - JOYSTICK_RADIUS,
+    );
+    let (center, drag) = touch.joystick.map_or((resting, Vec2::ZERO), |joystick| {
+        (
+            joystick.origin,
+            (joystick.position - joystick.origin).clamp_length_max(JOYSTICK_RADIUS),
+        )
+    });
+    base.left = Val::Px(center.x - JOYSTICK_RADIUS);
+    base.top = Val::Px(center.y - JOYSTICK_RADIUS);
+    // Relative to the base's padding box, inside its border
+    let knob_corner = Vec2::splat(JOYSTICK_RADIUS - KNOB_RADIUS - 2.0) + drag;
+    knob.left = Val::Px(knob_corner.x);
+    knob.top = Val::Px(knob_corner.y);
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::level::{
+        LevelAssets,
+        enemies::Enemy,
+        waves::{Minion, WaveProgress},
+    },
+    screens::Screen,
+    ui::{menus::Menu, theme::palette::LABEL_TEXT},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_wave_label)
+        .add_systems(
+            Update,
+            update_wave_label
+                .run_if(in_state(Screen::Gameplay))
+                .run_if(in_state(Menu::None)),
+        );
+}
+
+/// Marker component for the wave progress text
+#[derive(Component)]
+struct WaveLabel;
+
+fn spawn_wave_label(mut cmd: Commands, assets: Res<LevelAssets>) {
+    cmd.spawn((
+        Name::new("Wave Label"),
+        WaveLabel,
+        GlobalZIndex(1),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(50.0),
+            top: Val::Px(50.0),
+            ..default()
+        },
+        Text::new(""),
+        TextFont {
+            font: assets.level_font.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        TextColor(LABEL_TEXT),
+        DespawnOnExit(Screen::Gameplay),
+        DespawnOnEnter(Menu::Win),
+    ));
+}
+
+/// "Wave 2/3 - 4 left", or nothing before the first wave and on levels without any
+fn update_wave_label(
+    progress: Option<Res<WaveProgress>>,
+    minions: Query<&Enemy, With<Minion>>,
+    mut label: Single<&mut Text, With<WaveLabel>>,
+) {
+    let text = match progress {
+        Some(progress) if progress.started > 0 => {
+            let alive = minions.iter().filter(|minion| minion.life > 0).count();
+            format!(
+                "Wave {}/{} - {} left",
+                progress.started,
+                progress.total,
+                alive + progress.pending.len()
+            )
+        }
+        _ => String::new(),
+    };
+    if label.0 != text {
+        label.0 = text;
+    }
+}
@@ -0,0 +1,239 @@
+//! The controls menu, opened from the settings menu.
+//!
+//! Click a binding, then press the key, mouse or gamepad button to use for it.
+//! A key replaces the keyboard and mouse bindings, a gamepad button the
+//! gamepad ones. An action already on that button swaps to the old one.
+//! Escape, or clicking any button of the menu, cancels. Every change is saved
+//! right away.
+
+use bevy::prelude::*;
+
+use crate::{
+    input::{Action, Binding, InputMap, back_just_pressed},
+    ui::{
+        menus::Menu,
+        theme::{
+            interaction::InteractionAssets,
+            palette::{
+                BUTTON_BACKGROUND, BUTTON_BORDER, BUTTON_HOVERED_BACKGROUND,
+                BUTTON_PRESSED_BACKGROUND, BUTTON_TEXT, NORMAL_TEXT_COLOR,
+            },
+            prelude::*,
+        },
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu)
+        .add_systems(OnExit(Menu::Controls), stop_rebinding);
+    app.add_systems(
+        Update,
+        go_back.run_if(
+            in_state(Menu::Controls)
+                .and(back_just_pressed)
+                .and(not(resource_exists::<Rebinding>)),
+        ),
+    );
+    app.add_systems(
+        Update,
+        (capture_rebinding, update_binding_labels)
+            .chain()
+            .run_if(in_state(Menu::Controls)),
+    );
+    app.add_observer(start_rebinding);
+}
+
+/// Waiting for the new key of this action
+#[derive(Resource, Debug)]
+struct Rebinding(Action);
+
+#[derive(Component, Debug)]
+struct BindingButton(Action);
+
+#[derive(Component, Debug)]
+struct BindingLabel(Action);
+
+fn spawn_controls_menu(mut commands: Commands, menu_asset: Res<InteractionAssets>) {
+    commands.spawn((
+        Name::new("Background Image"),
+        GlobalZIndex(2),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        ImageNode {
+            image: menu_asset.cover.clone(),
+            ..default()
+        },
+        DespawnOnExit(Menu::Controls),
+    ));
+    let font = menu_asset.settings_font.clone();
+    commands
+        .spawn((
+            widget::ui_root("Controls Menu"),
+            GlobalZIndex(3),
+            DespawnOnExit(Menu::Controls),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::header_with_color("Controls", NORMAL_TEXT_COLOR));
+            root.spawn((
+                Name::new("Controls Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: px(8),
+                    column_gap: px(20),
+                    // Two actions per row
+                    grid_template_columns: RepeatedGridTrack::px(4, 200.0),
+                    ..default()
+                },
+            ))
+            .with_children(|grid| {
+                for action in Action::ALL {
+                    grid.spawn((
+                        widget::label_with_size_and_color(action.label(), 20.0, NORMAL_TEXT_COLOR),
+                        Node {
+                            justify_self: JustifySelf::End,
+                            ..default()
+                        },
+                    ));
+                    grid.spawn(binding_button(action, font.clone()));
+                }
+            });
+            root.spawn(widget::button("Reset", reset_bindings));
+            root.spawn(widget::button("Back", go_back_on_click));
+        });
+}
+
+fn binding_button(action: Action, font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new(format!("{} Binding", action.label())),
+        BindingButton(action),
+        Button,
+        Node {
+            justify_self: JustifySelf::Start,
+            justify_content: JustifyContent::Center,
+            min_width: px(180),
+            padding: UiRect::horizontal(px(8)),
+            border: UiRect::all(px(2)),
+            ..default()
+        },
+        BackgroundColor(BUTTON_BACKGROUND),
+        BorderColor::all(BUTTON_BORDER),
+        InteractionPalette {
+            none: BUTTON_BACKGROUND,
+            hovered: BUTTON_HOVERED_BACKGROUND,
+            pressed: BUTTON_PRESSED_BACKGROUND,
+        },
+        children![(
+            Name::new("Binding Text"),
+            BindingLabel(action),
+            Text::new(""),
+            TextFont {
+                font,
+                font_size: 20.0,
+                ..default()
+            },
+            TextColor(BUTTON_TEXT),
+            // Clicks go to the button
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+fn start_rebinding(
+    click: On<Pointer<Click>>,
+    mut commands: Commands,
+    buttons: Query<&BindingButton>,
+) {
+    if let Ok(button) = buttons.get(click.event_target()) {
+        commands.insert_resource(Rebinding(button.0));
+    }
+}
+
+fn stop_rebinding(mut commands: Commands) {
+    commands.remove_resource::<Rebinding>();
+}
+
+fn capture_rebinding(
+    mut commands: Commands,
+    rebinding: Option<Res<Rebinding>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    gamepads: Query<&Gamepad>,
+    buttons: Query<&Interaction, With<Button>>,
+    mut map: ResMut<InputMap>,
+) {
+    let Some(rebinding) = rebinding else {
+        return;
+    };
+    // Not the click that started it
+    if rebinding.is_added() {
+        return;
+    }
+    // A click on Back, Reset or another binding is meant for the button
+    let clicked_button = mouse.get_just_pressed().next().is_some()
+        && buttons
+            .iter()
+            .any(|interaction| *interaction != Interaction::None);
+    if keys.just_pressed(KeyCode::Escape) || clicked_button {
+        commands.remove_resource::<Rebinding>();
+        return;
+    }
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key| Binding::Key(*key))
+        .or_else(|| {
+            mouse
+                .get_just_pressed()
+                .next()
+                .map(|button| Binding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepads.iter().find_map(|gamepad| {
+                gamepad
+                    .get_just_pressed()
+                    .next()
+                    .map(|button| Binding::Gamepad(*button))
+            })
+        });
+    if let Some(binding) = binding {
+        map.rebind(rebinding.0, binding);
+        commands.remove_resource::<Rebinding>();
+    }
+}
+
+fn update_binding_labels(
+    map: Res<InputMap>,
+    rebinding: Option<Res<Rebinding>>,
+    mut labels: Query<(&mut Text, &BindingLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        let new = if rebinding.as_ref().is_some_and(|r| r.0 == label.0) {
+            "Press a button...".to_string()
+        } else {
+            map.get(label.0)
+                .iter()
+                .map(Binding::label)
+                .collect::<Vec<_>>()
+                .join(" / ")
+        };
+        if text.0 != new {
+            text.0 = new;
+        }
+    }
+}
+
+fn reset_bindings(_: On<Pointer<Click>>, mut map: ResMut<InputMap>) {
+    *map = InputMap::default();
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
@@ -1,10 +1,11 @@
 //! The credits menu.
 
-use bevy::{ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{ecs::spawn::SpawnIter, prelude::*};
 
 use crate::{
     asset_tracking::LoadResource,
     audio::music,
+    input::back_just_pressed,
     screens::Screen,
     ui::{
         menus::Menu,
@@ -20,7 +21,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Credits), spawn_credits_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Credits).and(input_just_pressed(KeyCode::Escape))),
+        go_back.run_if(in_state(Menu::Credits).and(back_just_pressed)),
     );
 
     app.load_resource::<CreditsAssets>();
@@ -0,0 +1,303 @@
+//! The difficulty menu, opened from the main menu.
+//!
+//! The arrows cycle the presets. Moving any of the numbers below them turns
+//! the difficulty into Custom.
+
+use bevy::{ecs::system::IntoObserverSystem, prelude::*};
+
+use crate::{
+    game::{
+        difficulty::{Difficulty, DifficultyScale},
+        run::RunMode,
+        save::{SaveData, Unlock},
+    },
+    input::back_just_pressed,
+    ui::{
+        menus::Menu,
+        theme::{interaction::InteractionAssets, palette::NORMAL_TEXT_COLOR, prelude::*},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Difficulty), spawn_difficulty_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Difficulty).and(back_just_pressed)),
+    );
+    app.add_systems(
+        Update,
+        update_difficulty_labels.run_if(in_state(Menu::Difficulty)),
+    );
+}
+
+const MULTIPLIER_STEP: f32 = 0.1;
+
+fn spawn_difficulty_menu(mut commands: Commands, menu_asset: Res<InteractionAssets>) {
+    commands.spawn((
+        Name::new("Background Image"),
+        GlobalZIndex(2),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        ImageNode {
+            image: menu_asset.cover.clone(),
+            ..default()
+        },
+        DespawnOnExit(Menu::Difficulty),
+    ));
+    let font = menu_asset.settings_font.clone();
+    commands.spawn((
+        widget::ui_root("Difficulty Menu"),
+        GlobalZIndex(3),
+        DespawnOnExit(Menu::Difficulty),
+        children![
+            widget::header_with_color("Difficulty", NORMAL_TEXT_COLOR),
+            (
+                Name::new("Difficulty Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: px(10),
+                    column_gap: px(30),
+                    grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+                    ..default()
+                },
+                children![
+                    row_label("Preset"),
+                    stepper(
+                        DifficultyLabel::Preset,
+                        "<",
+                        ">",
+                        previous_preset,
+                        next_preset,
+                        font.clone()
+                    ),
+                    row_label("Boss Life"),
+                    stepper(
+                        DifficultyLabel::BossLife,
+                        "-",
+                        "+",
+                        lower_boss_life,
+                        raise_boss_life,
+                        font.clone()
+                    ),
+                    row_label("Bullet Speed"),
+                    stepper(
+                        DifficultyLabel::BulletSpeed,
+                        "-",
+                        "+",
+                        lower_bullet_speed,
+                        raise_bullet_speed,
+                        font.clone()
+                    ),
+                    row_label("Bullet Density"),
+                    stepper(
+                        DifficultyLabel::BulletDensity,
+                        "-",
+                        "+",
+                        lower_bullet_density,
+                        raise_bullet_density,
+                        font.clone()
+                    ),
+                    row_label("Hearts & Ammo"),
+                    stepper(
+                        DifficultyLabel::PlayerStats,
+                        "-",
+                        "+",
+                        lower_player_stats,
+                        raise_player_stats,
+                        font.clone()
+                    ),
+                    row_label("Carry Over Hearts"),
+                    stepper(
+                        DifficultyLabel::RunMode,
+                        "<",
+                        ">",
+                        toggle_run_mode,
+                        toggle_run_mode,
+                        font
+                    ),
+                ],
+            ),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn row_label(text: &'static str) -> impl Bundle {
+    (
+        widget::label_with_size_and_color(text, 24.0, NORMAL_TEXT_COLOR),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+fn stepper<E1, B1, M1, E2, B2, M2>(
+    label: DifficultyLabel,
+    lower_text: &'static str,
+    raise_text: &'static str,
+    lower: impl IntoObserverSystem<E1, B1, M1>,
+    raise: impl IntoObserverSystem<E2, B2, M2>,
+    font: Handle<Font>,
+) -> impl Bundle
+where
+    E1: EntityEvent,
+    B1: Bundle,
+    E2: EntityEvent,
+    B2: Bundle,
+{
+    (
+        Name::new(format!("{label:?} Widget")),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small_custom_font(lower_text, lower, font.clone()),
+            (
+                Name::new(format!("Current {label:?}")),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(
+                    widget::label_with_size_and_color("", 24.0, NORMAL_TEXT_COLOR),
+                    label
+                )],
+            ),
+            widget::button_small_custom_font(raise_text, raise, font),
+        ],
+    )
+}
+
+fn previous_preset(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.previous();
+}
+
+fn next_preset(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.next();
+}
+
+/// Any change to the numbers makes it Custom
+fn customize(difficulty: &mut Difficulty, change: impl FnOnce(&mut DifficultyScale)) {
+    let mut scale = difficulty.scale();
+    change(&mut scale);
+    *difficulty = Difficulty::Custom(scale);
+}
+
+fn step_multiplier(value: &mut f32, step: f32) {
+    let (min, max) = DifficultyScale::MULTIPLIER_RANGE;
+    // Rounded so repeated steps don't drift
+    *value = ((*value + step) * 10.0)
+        .round()
+        .clamp(min * 10.0, max * 10.0)
+        / 10.0;
+}
+
+fn step_player_stats(value: &mut i32, step: i32) {
+    let (min, max) = DifficultyScale::PLAYER_STATS_RANGE;
+    *value = (*value + step).clamp(min, max);
+}
+
+fn lower_boss_life(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_multiplier(&mut s.boss_life, -MULTIPLIER_STEP)
+    });
+}
+
+fn raise_boss_life(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_multiplier(&mut s.boss_life, MULTIPLIER_STEP)
+    });
+}
+
+fn lower_bullet_speed(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_multiplier(&mut s.bullet_speed, -MULTIPLIER_STEP)
+    });
+}
+
+fn raise_bullet_speed(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_multiplier(&mut s.bullet_speed, MULTIPLIER_STEP)
+    });
+}
+
+fn lower_bullet_density(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_multiplier(&mut s.bullet_density, -MULTIPLIER_STEP)
+    });
+}
+
+fn raise_bullet_density(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_multiplier(&mut s.bullet_density, MULTIPLIER_STEP)
+    });
+}
+
+fn lower_player_stats(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_player_stats(&mut s.player_stats, -1)
+    });
+}
+
+fn raise_player_stats(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    customize(&mut difficulty, |s| {
+        step_player_stats(&mut s.player_stats, 1)
+    });
+}
+
+/// Not part of the difficulty presets, it only lives in this menu
+fn toggle_run_mode(_: On<Pointer<Click>>, save: Res<SaveData>, mut mode: ResMut<RunMode>) {
+    if save.is_unlocked(Unlock::RunMode) {
+        mode.0 = !mode.0;
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug)]
+#[reflect(Component)]
+enum DifficultyLabel {
+    Preset,
+    BossLife,
+    BulletSpeed,
+    BulletDensity,
+    PlayerStats,
+    RunMode,
+}
+
+fn update_difficulty_labels(
+    difficulty: Res<Difficulty>,
+    mode: Res<RunMode>,
+    save: Res<SaveData>,
+    mut labels: Query<(&mut Text, &DifficultyLabel)>,
+) {
+    let scale = difficulty.scale();
+    for (mut text, label) in &mut labels {
+        text.0 = match label {
+            DifficultyLabel::Preset => difficulty.label().to_string(),
+            DifficultyLabel::BossLife => format!("x{:.1}", scale.boss_life),
+            DifficultyLabel::BulletSpeed => format!("x{:.1}", scale.bullet_speed),
+            DifficultyLabel::BulletDensity => format!("x{:.1}", scale.bullet_density),
+            DifficultyLabel::PlayerStats => format!("{:+}", scale.player_stats),
+            DifficultyLabel::RunMode => match (save.is_unlocked(Unlock::RunMode), mode.0) {
+                (false, _) => "Locked",
+                (true, true) => "On",
+                (true, false) => "Off",
+            }
+            .to_string(),
+        };
+    }
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use crate::{
     asset_tracking::LoadResource,
     audio::music,
+    game::{difficulty::Difficulty, run::RunState},
     screens::Screen,
     ui::{
         menus::Menu,
@@ -18,7 +19,7 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn spawn_game_over(mut cmd: Commands, assets: Res<InteractionAssets>) {
+fn spawn_game_over(mut cmd: Commands, assets: Res<InteractionAssets>, difficulty: Res<Difficulty>) {
     cmd.spawn((
         widget::ui_root("Game Over Menu"),
         GlobalZIndex(2),
@@ -51,6 +52,7 @@ fn spawn_game_over(mut cmd: Commands, assets: Res<InteractionAssets>) {
                 BackgroundColor(BACKGROUND_DARK.with_alpha(0.6)),
                 children![
                     widget::header("You Died"),
+                    widget::label(format!("Difficulty: {}", difficulty.label())),
                     widget::button("Retry", retry_level),
                     widget::button("Quit to title", return_to_main),
                 ],
@@ -87,6 +89,11 @@ fn return_to_main(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Scree
     next_screen.set(Screen::Title);
 }
 
-fn retry_level(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+fn retry_level(
+    _: On<Pointer<Click>>,
+    mut run: ResMut<RunState>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    run.retry_level();
     next_screen.set(Screen::Loading);
 }
@@ -0,0 +1,129 @@
+//! The level select menu, opened from the main menu.
+//!
+//! Lists every level of the current [`BossFlow`] with its boss and best time at
+//! the current difficulty.
+//! Levels past the furthest one reached stay locked.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        difficulty::Difficulty,
+        level::{
+            Level,
+            boss_definition::{BossDefinition, BossDefinitions},
+            bosses::BossFlow,
+        },
+        save::SaveData,
+    },
+    input::back_just_pressed,
+    screens::Screen,
+    ui::{
+        menus::{Menu, win::format_secs},
+        theme::{interaction::InteractionAssets, palette::NORMAL_TEXT_COLOR, prelude::*},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::LevelSelect), spawn_level_select_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::LevelSelect).and(back_just_pressed)),
+    );
+}
+
+fn spawn_level_select_menu(
+    mut commands: Commands,
+    menu_asset: Res<InteractionAssets>,
+    save: Res<SaveData>,
+    flow: Res<BossFlow>,
+    difficulty: Res<Difficulty>,
+    boss_definitions: Option<Res<BossDefinitions>>,
+    definitions: Res<Assets<BossDefinition>>,
+) {
+    commands.spawn((
+        Name::new("Background Image"),
+        GlobalZIndex(2),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        ImageNode {
+            image: menu_asset.cover.clone(),
+            ..default()
+        },
+        DespawnOnExit(Menu::LevelSelect),
+    ));
+    let levels = Level::ALL
+        .into_iter()
+        .filter(|level| *level <= flow.last_level());
+    let rows: Vec<(Level, String, String, bool)> = levels
+        .map(|level| {
+            let boss = boss_definitions
+                .as_ref()
+                .and_then(|bosses| definitions.get(bosses.for_level(level, *flow)))
+                .map_or("???".to_string(), |definition| definition.name.clone());
+            let best = save
+                .best_time(level, *flow, *difficulty)
+                .map_or("--:--".to_string(), format_secs);
+            (level, boss, best, save.is_level_unlocked(level))
+        })
+        .collect();
+    commands
+        .spawn((
+            widget::ui_root("Level Select Menu"),
+            GlobalZIndex(3),
+            DespawnOnExit(Menu::LevelSelect),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::header_with_color("Levels", NORMAL_TEXT_COLOR));
+            root.spawn((
+                Name::new("Level Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: px(10),
+                    column_gap: px(30),
+                    align_items: AlignItems::Center,
+                    grid_template_columns: RepeatedGridTrack::auto(3),
+                    ..default()
+                },
+            ))
+            .with_children(|grid| {
+                for (level, boss, best, unlocked) in rows {
+                    if unlocked {
+                        grid.spawn(widget::button(level.label(), play_level(level)));
+                        grid.spawn(widget::label_with_size_and_color(
+                            boss,
+                            24.0,
+                            NORMAL_TEXT_COLOR,
+                        ));
+                    } else {
+                        grid.spawn(widget::label_with_size("Locked", 24.0));
+                        grid.spawn(widget::label_with_size("???", 24.0));
+                    }
+                    grid.spawn(widget::label_with_size(best, 24.0));
+                }
+            });
+            root.spawn(widget::button("Back", go_back_on_click));
+        });
+}
+
+/// Starts a fresh run from `level`
+fn play_level(
+    level: Level,
+) -> impl Fn(On<Pointer<Click>>, ResMut<NextState<Level>>, ResMut<NextState<Screen>>) {
+    move |_, mut next_level, mut next_screen| {
+        next_level.set(level);
+        next_screen.set(Screen::Loading);
+    }
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
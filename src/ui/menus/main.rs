@@ -1,9 +1,13 @@
 //! The main menu (seen on the title screen).
 
-use bevy::prelude::*;
+use bevy::{ecs::spawn::SpawnWith, prelude::*};
 
 use crate::{
     asset_tracking::ResourceHandles,
+    game::{
+        level::{Level, bosses::BossFlow},
+        save::SaveData,
+    },
     screens::Screen,
     ui::{
         menus::Menu,
@@ -15,7 +19,8 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
 }
 
-fn spawn_main_menu(mut commands: Commands, assets: Res<InteractionAssets>) {
+fn spawn_main_menu(mut commands: Commands, assets: Res<InteractionAssets>, save: Res<SaveData>) {
+    let has_progress = save.has_progress();
     commands.spawn((
         widget::menu_root("Main Menu"),
         GlobalZIndex(2),
@@ -47,19 +52,18 @@ fn spawn_main_menu(mut commands: Commands, assets: Res<InteractionAssets>) {
                     ..default()
                 },
                 BackgroundColor(BACKGROUND_DARK.with_alpha(0.6)),
-                #[cfg(not(target_family = "wasm"))]
-                children![
-                    widget::button("Play", enter_loading_or_gameplay_screen),
-                    widget::button("Settings", open_settings_menu),
-                    widget::button("Credits", open_credits_menu),
-                    widget::button("Exit", exit_app),
-                ],
-                #[cfg(target_family = "wasm")]
-                children![
-                    widget::button("Play", enter_loading_or_gameplay_screen),
-                    widget::button("Settings", open_settings_menu),
-                    widget::button("Credits", open_credits_menu),
-                ],
+                Children::spawn(SpawnWith(move |sidebar: &mut ChildSpawner| {
+                    if has_progress {
+                        sidebar.spawn(widget::button("Continue", continue_game));
+                    }
+                    sidebar.spawn(widget::button("Play", enter_loading_or_gameplay_screen));
+                    sidebar.spawn(widget::button("Levels", open_level_select_menu));
+                    sidebar.spawn(widget::button("Difficulty", open_difficulty_menu));
+                    sidebar.spawn(widget::button("Settings", open_settings_menu));
+                    sidebar.spawn(widget::button("Credits", open_credits_menu));
+                    #[cfg(not(target_family = "wasm"))]
+                    sidebar.spawn(widget::button("Exit", exit_app));
+                })),
             ),
         ],
     ));
@@ -72,6 +76,26 @@ fn enter_loading_or_gameplay_screen(
     next_screen.set(Screen::Loading);
 }
 
+/// Straight to the furthest level reached that the boss flow has
+fn continue_game(
+    _: On<Pointer<Click>>,
+    save: Res<SaveData>,
+    flow: Res<BossFlow>,
+    mut next_level: ResMut<NextState<Level>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    next_level.set(save.highest_level.min(flow.last_level()));
+    next_screen.set(Screen::Loading);
+}
+
+fn open_level_select_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::LevelSelect);
+}
+
+fn open_difficulty_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Difficulty);
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
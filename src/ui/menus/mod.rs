@@ -1,10 +1,15 @@
 //! The game's menus and transitions between them.
 
+mod controls;
 mod credits;
+mod difficulty;
 mod game_over;
+mod level_select;
 mod main;
+mod navigation;
 mod pause;
 mod settings;
+mod upgrade;
 mod win;
 
 use bevy::prelude::*;
@@ -13,11 +18,16 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        controls::plugin,
         credits::plugin,
+        difficulty::plugin,
         main::plugin,
+        navigation::plugin,
         settings::plugin,
         pause::plugin,
         game_over::plugin,
+        level_select::plugin,
+        upgrade::plugin,
         win::plugin,
     ));
 }
@@ -29,7 +39,11 @@ pub enum Menu {
     Main,
     Credits,
     Settings,
+    Controls,
+    Difficulty,
+    LevelSelect,
     Pause,
     GameOver,
+    Upgrade,
     Win,
 }
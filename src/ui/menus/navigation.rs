@@ -0,0 +1,153 @@
+//! Gamepad navigation of the menus.
+//!
+//! The d-pad or left stick moves the focus between the buttons in reading
+//! order and South clicks the focused one. Focus and clicks go through the
+//! usual pointer events, so buttons light up and play sounds like with a mouse.
+
+use std::time::Duration;
+
+use bevy::{
+    camera::NormalizedRenderTarget,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+    ui::UiGlobalTransform,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::{input::STICK_DEADZONE, ui::menus::Menu};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (move_focus, click_focused)
+            .chain()
+            .run_if(not(in_state(Menu::None))),
+    );
+}
+
+/// The button the gamepad is on
+#[derive(Component, Debug)]
+struct GamepadFocus;
+
+/// How far the stick has to be tilted to move the focus
+const STICK_STEP: f32 = 0.5;
+
+fn move_focus(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    buttons: Query<(Entity, &UiGlobalTransform, &InheritedVisibility), With<Button>>,
+    focused: Query<Entity, With<GamepadFocus>>,
+    window: Single<Entity, With<PrimaryWindow>>,
+    // The stick moves the focus once per tilt
+    mut stick_held: Local<bool>,
+) {
+    let mut step = 0;
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadDown)
+            || gamepad.just_pressed(GamepadButton::DPadRight)
+        {
+            step = 1;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadUp)
+            || gamepad.just_pressed(GamepadButton::DPadLeft)
+        {
+            step = -1;
+        }
+    }
+    let stick = gamepads
+        .iter()
+        .map(Gamepad::left_stick)
+        .find(|v| v.length() > STICK_DEADZONE)
+        .unwrap_or(Vec2::ZERO);
+    if stick.length() < STICK_STEP {
+        *stick_held = false;
+    } else if !*stick_held {
+        *stick_held = true;
+        // The stick's y points up, the UI's down
+        step = if stick.y.abs() > stick.x.abs() {
+            -stick.y.signum() as i32
+        } else {
+            stick.x.signum() as i32
+        };
+    }
+    if step == 0 {
+        return;
+    }
+
+    let mut order: Vec<(Entity, Vec2)> = buttons
+        .iter()
+        .filter(|(_, _, visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation))
+        .collect();
+    if order.is_empty() {
+        return;
+    }
+    // Rows first, rounded so a pixel of misalignment doesn't split a row
+    order.sort_by(|(_, a), (_, b)| {
+        (a.y.round(), a.x)
+            .partial_cmp(&(b.y.round(), b.x))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let current = focused.iter().next();
+    let next = match current.and_then(|c| order.iter().position(|(e, _)| *e == c)) {
+        Some(index) => (index as i32 + step).rem_euclid(order.len() as i32) as usize,
+        None => 0,
+    };
+    let next = order[next].0;
+    if current == Some(next) {
+        return;
+    }
+    if let Some(current) = current {
+        commands.entity(current).remove::<GamepadFocus>();
+        commands.trigger(pointer_event(Out { hit: hit() }, current, *window));
+    }
+    commands.entity(next).insert(GamepadFocus);
+    commands.trigger(pointer_event(Over { hit: hit() }, next, *window));
+}
+
+fn click_focused(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    focused: Query<Entity, With<GamepadFocus>>,
+    window: Single<Entity, With<PrimaryWindow>>,
+) {
+    if !gamepads
+        .iter()
+        .any(|g| g.just_pressed(GamepadButton::South))
+    {
+        return;
+    }
+    for entity in &focused {
+        let click = Click {
+            button: PointerButton::Primary,
+            hit: hit(),
+            duration: Duration::ZERO,
+        };
+        commands.trigger(pointer_event(click, entity, *window));
+    }
+}
+
+fn hit() -> HitData {
+    HitData::new(Entity::PLACEHOLDER, 0.0, None, None)
+}
+
+/// A pointer event on `entity` as if the mouse did it
+fn pointer_event<E: std::fmt::Debug + Clone + Reflect>(
+    event: E,
+    entity: Entity,
+    window: Entity,
+) -> Pointer<E> {
+    let location = Location {
+        target: NormalizedRenderTarget::Window(
+            WindowRef::Primary
+                .normalize(Some(window))
+                .expect("The primary window exists"),
+        ),
+        position: Vec2::ZERO,
+    };
+    Pointer::new(PointerId::Mouse, location, event, entity)
+}
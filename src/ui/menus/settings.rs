@@ -2,9 +2,11 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{audio::Volume, prelude::*};
 
 use crate::{
+    game::level::bosses::BossFlow,
+    input::back_just_pressed,
     screens::Screen,
     ui::{
         menus::Menu,
@@ -20,12 +22,12 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+        go_back.run_if(in_state(Menu::Settings).and(back_just_pressed)),
     );
 
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (update_global_volume_label, update_boss_flow_label).run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -52,6 +54,7 @@ fn spawn_settings_menu(mut commands: Commands, menu_asset: Res<InteractionAssets
         children![
             widget::header_with_color("Settings", NORMAL_TEXT_COLOR),
             settings_grid(menu_asset.settings_font.clone()),
+            widget::button("Controls", open_controls),
             widget::button("Back", go_back_on_click),
         ],
     ));
@@ -75,7 +78,15 @@ fn settings_grid(font: Handle<Font>) -> impl Bundle {
                     ..default()
                 }
             ),
-            global_volume_widget(font),
+            global_volume_widget(font.clone()),
+            (
+                widget::label_with_size_and_color("Boss Fight", 24.0, NORMAL_TEXT_COLOR),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            boss_flow_widget(font),
         ],
     )
 }
@@ -106,6 +117,32 @@ fn global_volume_widget(font: Handle<Font>) -> impl Bundle {
     )
 }
 
+fn boss_flow_widget(font: Handle<Font>) -> impl Bundle {
+    (
+        Name::new("Boss Flow Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small_custom_font("<", toggle_boss_flow, font.clone()),
+            (
+                Name::new("Current Boss Flow"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(
+                    widget::label_with_size_and_color("", 24.0, NORMAL_TEXT_COLOR),
+                    BossFlowLabel
+                )],
+            ),
+            widget::button_small_custom_font(">", toggle_boss_flow, font),
+        ],
+    )
+}
+
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
 
@@ -131,6 +168,23 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+/// Only two flows, so either arrow switches to the other one
+fn toggle_boss_flow(_: On<Pointer<Click>>, mut flow: ResMut<BossFlow>) {
+    *flow = flow.toggled();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BossFlowLabel;
+
+fn update_boss_flow_label(flow: Res<BossFlow>, mut label: Single<&mut Text, With<BossFlowLabel>>) {
+    label.0 = flow.label().to_string();
+}
+
+fn open_controls(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Controls);
+}
+
 fn go_back_on_click(
     _: On<Pointer<Click>>,
     screen: Res<State<Screen>>,
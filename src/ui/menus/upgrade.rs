@@ -0,0 +1,66 @@
+//! The upgrade menu, shown between a defeated boss and the next level.
+//!
+//! Offers a random pick of upgrades. The chosen one joins the run and the
+//! next level loads.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{level::Level, rng::GameRng, run::RunState, upgrades::Upgrade},
+    screens::Screen,
+    ui::{
+        menus::Menu,
+        theme::{palette::BACKGROUND_DARK, widget},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Upgrade), spawn_upgrade_menu);
+}
+
+fn spawn_upgrade_menu(mut commands: Commands, mut rng: ResMut<GameRng>) {
+    let choices = Upgrade::roll_choices(&mut rng);
+    commands
+        .spawn((
+            widget::ui_root("Upgrade Menu"),
+            // Above the level transition overlay
+            GlobalZIndex(6),
+            BackgroundColor(BACKGROUND_DARK.with_alpha(0.9)),
+            DespawnOnExit(Menu::Upgrade),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::header("Choose a Blessing"));
+            for upgrade in choices {
+                root.spawn((
+                    Name::new(format!("{} Choice", upgrade.label())),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: px(6),
+                        ..default()
+                    },
+                    children![
+                        widget::button(upgrade.label(), choose(upgrade)),
+                        widget::label(upgrade.description()),
+                    ],
+                ));
+            }
+        });
+}
+
+/// Keeps `upgrade` for the rest of the run and loads the next level
+fn choose(
+    upgrade: Upgrade,
+) -> impl Fn(
+    On<Pointer<Click>>,
+    ResMut<RunState>,
+    Res<State<Level>>,
+    ResMut<NextState<Level>>,
+    ResMut<NextState<Screen>>,
+) {
+    move |_, mut run, current_level, mut next_level, mut next_screen| {
+        run.upgrades.push(upgrade);
+        next_level.set(current_level.next());
+        next_screen.set(Screen::Loading);
+    }
+}
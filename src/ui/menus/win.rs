@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use crate::{
     asset_tracking::LoadResource,
     audio::music,
+    game::{difficulty::Difficulty, run::RunState},
     screens::Screen,
     ui::{
         menus::Menu,
@@ -15,7 +16,12 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Win), (spawn_win, start_win_music));
 }
 
-fn spawn_win(mut cmd: Commands, assets: Res<InteractionAssets>) {
+fn spawn_win(
+    mut cmd: Commands,
+    assets: Res<InteractionAssets>,
+    difficulty: Res<Difficulty>,
+    run: Res<RunState>,
+) {
     cmd.spawn((
         widget::ui_root("All cleared"),
         GlobalZIndex(2),
@@ -48,6 +54,8 @@ fn spawn_win(mut cmd: Commands, assets: Res<InteractionAssets>) {
                 BackgroundColor(BACKGROUND_DARK.with_alpha(0.6)),
                 children![
                     widget::header("Narak Slayed"),
+                    widget::label(format!("Difficulty: {}", difficulty.label())),
+                    widget::label(format!("Time: {}", format_secs(run.elapsed_secs))),
                     widget::button("Credits", show_credits),
                     widget::button("Quit to title", return_to_main),
                 ],
@@ -56,6 +64,12 @@ fn spawn_win(mut cmd: Commands, assets: Res<InteractionAssets>) {
     ));
 }
 
+/// As minutes and seconds, `3:07`
+pub fn format_secs(secs: f32) -> String {
+    let secs = secs.max(0.0) as u32;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 struct WinAssets {
@@ -1,5 +1,4 @@
-/*
-use avian2d::prelude::PhysicsLayer;
+use avian2d::prelude::*;
 
 #[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
 pub enum GameLayer {
@@ -12,6 +11,17 @@ pub enum GameLayer {
     Pickups,      // Layer 5
 }
 
+/// Only the pickups use their layer so far. The player keeps the default
+/// layer too, so it still meets everything that has no layers yet.
+pub fn player_layers() -> CollisionLayers {
+    CollisionLayers::new([GameLayer::Walls, GameLayer::Player], LayerMask::ALL)
+}
+
+pub fn pickup_layers() -> CollisionLayers {
+    CollisionLayers::new(GameLayer::Pickups, GameLayer::Player)
+}
+
+/*
 
 * Layer        | Collides with
 * --
@@ -0,0 +1,64 @@
+//! Small RON files kept in the platform config directory, e.g. the key bindings.
+//! There is no file system on the web, so nothing is read or written there.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Folder under the platform config directory
+const APP_DIR: &str = env!("CARGO_PKG_NAME");
+
+/// [`APP_DIR`] in `~/.config` on Linux, `%APPDATA%` on Windows and
+/// `~/Library/Application Support` on macOS
+#[cfg(not(target_family = "wasm"))]
+pub fn config_dir() -> Option<PathBuf> {
+    let env_dir = |var: &str| std::env::var_os(var).map(PathBuf::from);
+    let base = if cfg!(target_os = "windows") {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+    };
+    base.map(|dir| dir.join(APP_DIR))
+}
+
+#[cfg(target_family = "wasm")]
+pub fn config_dir() -> Option<PathBuf> {
+    None
+}
+
+/// `None` if the file is missing or can't be parsed
+pub fn read_config<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    let path = config_dir()?.join(file_name);
+    let text = std::fs::read_to_string(&path).ok()?;
+    match ron::from_str(&text) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("Ignoring {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Written next to the file first and then renamed over it, so a crash or a
+/// full disk mid-write leaves the old file whole
+pub fn write_config<T: Serialize>(file_name: &str, value: &T) {
+    let Some(dir) = config_dir() else {
+        return;
+    };
+    let path = dir.join(file_name);
+    let temp_path = dir.join(format!("{file_name}.tmp"));
+    let result = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|e| e.to_string())
+        .and_then(|text| {
+            std::fs::create_dir_all(&dir)
+                .and_then(|_| std::fs::write(&temp_path, text))
+                .and_then(|_| std::fs::rename(&temp_path, &path))
+                .map_err(|e| e.to_string())
+        });
+    if let Err(e) = result {
+        warn!("Could not save {}: {e}", path.display());
+    }
+}
@@ -1,6 +1,7 @@
 //! Stuffs that haven't been on the three main directories in the original template
 pub mod cam;
 pub mod collisions_layers;
+pub mod config;
 pub mod tiled; // Named to be distinct from bevy::camera
 
 use bevy::prelude::*;
@@ -11,7 +11,8 @@
 //
 // Functional limitations:
 //   * When the 'atlas' feature is enabled tilesets using a collection of images will be skipped.
-//   * Only finite tile layers are loaded. Infinite tile layers and object layers will be skipped.
+//   * Only finite tile layers are loaded. Infinite tile layers and object layers will be skipped,
+//     except for objects of class `SpawnPoint`, kept in `TiledMap::spawn_points`.
 mod shaper;
 
 use std::io::Cursor;
@@ -73,6 +74,9 @@ pub fn spawn_tiled_map(
     ));
 }
 
+/// Class of the objects marking where wave minions come in
+pub const SPAWN_POINT_CLASS: &str = "SpawnPoint";
+
 pub struct Properties {
     is_projectile_passthrough: bool,
 }
@@ -92,6 +96,9 @@ pub struct TiledMap {
     pub pre_colliders: HashMap<tiled::TileId, PreSharedShape>,
     pub pre_properties: HashMap<tiled::TileId, Properties>,
     pub tilemap_textures: HashMap<usize, TilemapTexture>,
+    /// World positions of the [`SPAWN_POINT_CLASS`] objects, in object layer
+    /// and id order
+    pub spawn_points: Vec<Vec2>,
 
     // The offset into the tileset_images for each tile id within each tileset.
     #[cfg(not(feature = "atlas"))]
@@ -281,11 +288,13 @@ impl AssetLoader for TiledLoader {
             tilemap_textures.insert(tileset_index, tilemap_texture);
         }
 
+        let spawn_points = spawn_points(&map);
         let asset_map = TiledMap {
             map,
             pre_colliders,
             pre_properties,
             tilemap_textures,
+            spawn_points,
             #[cfg(not(feature = "atlas"))]
             tile_image_offsets,
         };
@@ -300,6 +309,30 @@ impl AssetLoader for TiledLoader {
     }
 }
 
+/// Objects are placed in pixels from the top left corner, the map is centered
+/// on the world origin with y up
+fn spawn_points(map: &tiled::Map) -> Vec<Vec2> {
+    let half_size = Vec2::new(
+        (map.width * map.tile_width) as f32,
+        (map.height * map.tile_height) as f32,
+    ) / 2.0;
+    let mut points = Vec::new();
+    for layer in map.layers() {
+        let tiled::LayerType::Objects(object_layer) = layer.layer_type() else {
+            continue;
+        };
+        for object in object_layer.objects() {
+            if object.user_type == SPAWN_POINT_CLASS {
+                points.push(Vec2::new(
+                    object.x + layer.offset_x - half_size.x,
+                    half_size.y - (object.y + layer.offset_y),
+                ));
+            }
+        }
+    }
+    points
+}
+
 fn process_loaded_maps(
     mut commands: Commands,
     mut map_events: MessageReader<AssetEvent<TiledMap>>,